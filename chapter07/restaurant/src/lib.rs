@@ -13,3 +13,42 @@ pub use crate::front_of_house::hosting;
 pub fn eat_at_restaurant() {
     hosting::add_to_waitlist();
 }
+
+// Seats in the restaurant. A party bigger than this can't be seated.
+pub const CAPACITY: usize = 20;
+
+// Like eat_at_restaurant, but the caller finds out whether the party got in
+pub fn try_eat(party_size: usize) -> Result<String, String> {
+    if party_size > CAPACITY {
+        return Err(format!(
+            "a party of {party_size} is too big, the restaurant seats {CAPACITY}"
+        ));
+    }
+
+    hosting::add_to_waitlist();
+    Ok(format!("Table for {party_size}, please follow me"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn party_within_capacity_is_seated() {
+        assert_eq!(
+            try_eat(4),
+            Ok(String::from("Table for 4, please follow me"))
+        );
+        assert!(try_eat(CAPACITY).is_ok());
+    }
+
+    #[test]
+    fn party_over_capacity_is_rejected() {
+        assert_eq!(
+            try_eat(CAPACITY + 1),
+            Err(String::from(
+                "a party of 21 is too big, the restaurant seats 20"
+            ))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// Propagating errors through a small parser
+// eval computes integer expressions such as "2 + 3 * 4", where every number and operator is
+// separated by spaces. Each grammar rule is a function that returns a Result, so the first
+// problem found is carried up to the caller with ? instead of panicking:
+//   sum     = product (("+" | "-") product)*
+//   product = number (("*" | "/") number)*
+// Because sum is built out of products, * and / bind tighter than + and -, and each loop
+// folds from the left, so "8 - 2 - 1" is (8 - 2) - 1.
+pub fn eval(expr: &str) -> Result<i64, String> {
+    let tokens: Vec<&str> = expr.split_whitespace().collect();
+    let mut pos = 0;
+
+    let value = parse_sum(&tokens, &mut pos)?;
+    match tokens.get(pos) {
+        None => Ok(value),
+        Some(token) => Err(format!("unexpected token {:?}", token)),
+    }
+}
+
+fn parse_sum(tokens: &[&str], pos: &mut usize) -> Result<i64, String> {
+    let mut value = parse_product(tokens, pos)?;
+
+    loop {
+        let op = match tokens.get(*pos) {
+            Some(&op) if op == "+" || op == "-" => op,
+            _ => return Ok(value),
+        };
+        *pos += 1;
+        let rhs = parse_product(tokens, pos)?;
+        let result = if op == "+" {
+            value.checked_add(rhs)
+        } else {
+            value.checked_sub(rhs)
+        };
+        value = result.ok_or_else(|| String::from("overflow"))?;
+    }
+}
+
+fn parse_product(tokens: &[&str], pos: &mut usize) -> Result<i64, String> {
+    let mut value = parse_number(tokens, pos)?;
+
+    loop {
+        let op = match tokens.get(*pos) {
+            Some(&op) if op == "*" || op == "/" => op,
+            _ => return Ok(value),
+        };
+        *pos += 1;
+        let rhs = parse_number(tokens, pos)?;
+        let result = if op == "*" {
+            value.checked_mul(rhs)
+        } else if rhs == 0 {
+            return Err(String::from("division by zero"));
+        } else {
+            value.checked_div(rhs)
+        };
+        value = result.ok_or_else(|| String::from("overflow"))?;
+    }
+}
+
+fn parse_number(tokens: &[&str], pos: &mut usize) -> Result<i64, String> {
+    let token = match tokens.get(*pos) {
+        Some(token) => token,
+        None => return Err(String::from("expected a number, found end of expression")),
+    };
+    *pos += 1;
+
+    token
+        .parse::<i64>()
+        .map_err(|_| format!("expected a number, found {:?}", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_respects_precedence() {
+        assert_eq!(eval("2 + 3 * 4"), Ok(14));
+        assert_eq!(eval("8 - 2 - 1"), Ok(5));
+        assert_eq!(eval("7 / 2 * 2"), Ok(6));
+    }
+
+    #[test]
+    fn eval_division_by_zero() {
+        assert_eq!(eval("1 + 4 / 0"), Err(String::from("division by zero")));
+    }
+
+    #[test]
+    fn eval_malformed_expressions() {
+        assert_eq!(
+            eval("2 + * 3"),
+            Err(String::from("expected a number, found \"*\""))
+        );
+        assert_eq!(
+            eval("2 +"),
+            Err(String::from("expected a number, found end of expression"))
+        );
+        assert_eq!(eval("2 3"), Err(String::from("unexpected token \"3\"")));
+    }
+}
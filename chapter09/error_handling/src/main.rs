@@ -1 +1,11 @@
-fn main() {}
+use error_handling::eval;
+
+fn main() {
+    // An error returned as a value can be reported without stopping the program
+    for expr in ["2 + 3 * 4", "1 / 0"] {
+        match eval(expr) {
+            Ok(value) => println!("{} = {}", expr, value),
+            Err(e) => println!("{} failed: {}", expr, e),
+        }
+    }
+}
@@ -121,3 +121,6 @@ fn read_username_from_file_3() -> Result<String, io::Error> {
 fn read_username_from_file_4() -> Result<String, io::Error> {
     return fs::read_to_string("hello.txt");
 }
+
+// Propagating errors through a small parser: see eval in lib.rs, which is built and
+// tested by cargo.
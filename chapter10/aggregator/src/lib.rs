@@ -17,11 +17,67 @@
 
 // Note that it isn’t possible to call the default implementation from an overriding implementation of that same method.
 
+use std::cmp::Reverse;
 use std::fmt::{Debug, Display};
+use std::io::{self, Write};
 
 pub trait Summary {
     // method summarize
     fn summarize(&self) -> String;
+
+    // Default method built on summarize: keeps at most max_chars chars and marks the cut
+    // with "…". A String can't be sliced at an arbitrary byte offset because a char may
+    // take several bytes, so char_indices finds the byte where char number max_chars starts.
+    fn summarize_truncated(&self, max_chars: usize) -> String {
+        let summary = self.summarize();
+        match summary.char_indices().nth(max_chars) {
+            Some((cut, _)) => format!("{}…", &summary[..cut]),
+            None => summary,
+        }
+    }
+
+    // Like summarize_truncated, but the result including the ellipsis is at most max_chars
+    // chars long. With at_word_boundary the cut moves back to the last whitespace so no
+    // word is split; a single word longer than the limit is still cut in the middle.
+    // When the ellipsis alone takes up the whole limit, there's no room for it next to any
+    // text, so the summary is cut to max_chars chars without one.
+    fn summarize_truncated_with(
+        &self,
+        max_chars: usize,
+        ellipsis: &str,
+        at_word_boundary: bool,
+    ) -> String {
+        let summary = self.summarize();
+        if summary.chars().count() <= max_chars {
+            return summary;
+        }
+
+        let ellipsis_chars = ellipsis.chars().count();
+        if ellipsis_chars >= max_chars {
+            return summary.chars().take(max_chars).collect();
+        }
+
+        let keep = max_chars - ellipsis_chars;
+        let mut cut = summary
+            .char_indices()
+            .nth(keep)
+            .map_or(summary.len(), |(i, _)| i);
+        let next_is_space = summary[cut..].starts_with(char::is_whitespace);
+        if at_word_boundary && !next_is_space {
+            if let Some(space) = summary[..cut].rfind(char::is_whitespace) {
+                cut = space;
+            }
+        }
+
+        format!("{}{}", summary[..cut].trim_end(), ellipsis)
+    }
+
+    // Appends the summary to buf. The default still builds the summary as its own String;
+    // a type can override it to write the parts straight into buf, so a loop reusing one
+    // buffer doesn't allocate for every item.
+    fn summarize_into(&self, buf: &mut String) {
+        buf.push_str(&self.summarize());
+    }
 }
 
 // Default implementation
@@ -41,6 +97,8 @@ pub struct NewsArticle {
     pub location: String,
     pub author: String,
     pub content: String,
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
 }
 
 impl Summary for NewsArticle {
@@ -52,18 +110,263 @@ impl Summary for NewsArticle {
 // Default implementation
 impl SummaryDefault for NewsArticle {}
 
+impl NewsArticle {
+    // Word-wraps content into lines of at most width chars, breaking only at whitespace.
+    // A word longer than width can't be split, so it gets a line of its own.
+    pub fn wrap_content(&self, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+
+        for word in self.content.split_whitespace() {
+            let needed = line.chars().count() + 1 + word.chars().count();
+            if !line.is_empty() && needed > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    // The content without basic markdown: **bold**, *italic*, `code` and [text](url)
+    // become just the text. A marker without its closing half is left as it is.
+    pub fn content_plain(&self) -> String {
+        strip_markdown(&self.content)
+    }
+}
+
+fn strip_markdown(text: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        // Bold and italic can hold other markup, code is kept exactly as written
+        let parsed = if rest.starts_with("**") {
+            between(rest, "**").map(|(inner, after)| (strip_markdown(inner), after))
+        } else if c == '*' {
+            between(rest, "*").map(|(inner, after)| (strip_markdown(inner), after))
+        } else if c == '`' {
+            between(rest, "`").map(|(inner, after)| (String::from(inner), after))
+        } else if c == '[' {
+            link(rest).map(|(label, after)| (strip_markdown(label), after))
+        } else {
+            None
+        };
+
+        match parsed {
+            Some((inner, after)) => {
+                plain.push_str(&inner);
+                rest = after;
+            }
+            None => {
+                let len = if rest.starts_with("**") {
+                    2
+                } else {
+                    c.len_utf8()
+                };
+                plain.push_str(&rest[..len]);
+                rest = &rest[len..];
+            }
+        }
+    }
+
+    plain
+}
+
+// Splits "<marker>inner<marker>rest" into inner and rest
+fn between<'a>(text: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let after = text.strip_prefix(marker)?;
+    let end = after.find(marker)?;
+    Some((&after[..end], &after[end + marker.len()..]))
+}
+
+// Splits "[label](url)rest" into label and rest
+fn link(text: &str) -> Option<(&str, &str)> {
+    let (label, after) = text.strip_prefix('[')?.split_once("](")?;
+    let (_url, after) = after.split_once(')')?;
+    Some((label, after))
+}
+
+// Builder for NewsArticle
+// The fields of NewsArticle stay public, but the builder gives a construction path that
+// checks the article makes sense before handing it out: build rejects an empty headline
+// or an empty author.
+#[derive(Default)]
+pub struct NewsArticleBuilder {
+    headline: String,
+    location: String,
+    author: String,
+    content: String,
+    timestamp: u64,
+}
+
+impl NewsArticleBuilder {
+    pub fn new() -> NewsArticleBuilder {
+        NewsArticleBuilder::default()
+    }
+
+    // Each setter takes ownership of the builder and returns it, so calls can be chained.
+    pub fn headline(mut self, headline: &str) -> NewsArticleBuilder {
+        self.headline = String::from(headline);
+        self
+    }
+
+    pub fn location(mut self, location: &str) -> NewsArticleBuilder {
+        self.location = String::from(location);
+        self
+    }
+
+    pub fn author(mut self, author: &str) -> NewsArticleBuilder {
+        self.author = String::from(author);
+        self
+    }
+
+    pub fn content(mut self, content: &str) -> NewsArticleBuilder {
+        self.content = String::from(content);
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: u64) -> NewsArticleBuilder {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> Result<NewsArticle, String> {
+        if self.headline.is_empty() {
+            return Err(String::from("a news article needs a headline"));
+        }
+        if self.author.is_empty() {
+            return Err(String::from("a news article needs an author"));
+        }
+
+        Ok(NewsArticle {
+            headline: self.headline,
+            location: self.location,
+            author: self.author,
+            content: self.content,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
 // For the Tweet struct, we define summarize as the username followed by the entire text of the tweet, assuming that tweet content is already limited to 280 characters.
 pub struct Tweet {
     pub username: String,
     pub content: String,
     pub reply: bool,
     pub retweet: bool,
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
 }
 
 impl Summary for Tweet {
     fn summarize(&self) -> String {
         return format!("{}: {}", self.username, self.content);
     }
+
+    fn summarize_into(&self, buf: &mut String) {
+        buf.push_str(&self.username);
+        buf.push_str(": ");
+        buf.push_str(&self.content);
+    }
+}
+
+// Hashtags and mentions
+// Both are words right after a symbol, returned lowercased and without the symbol.
+impl Tweet {
+    pub fn hashtags(&self) -> Vec<String> {
+        tagged_words(&self.content, '#')
+    }
+
+    pub fn mentions(&self) -> Vec<String> {
+        tagged_words(&self.content, '@')
+    }
+}
+
+// Words are made of letters, digits and underscores; whitespace and punctuation end them.
+// Only tokens that start with the symbol count, so "me@example.com" is not a mention, and
+// a symbol with nothing after it ("#" at the end) is skipped.
+fn tagged_words(text: &str, symbol: char) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == symbol))
+        .filter_map(|token| token.strip_prefix(symbol))
+        .filter_map(|rest| rest.split(symbol).next())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+// Escapes the characters that mean something in HTML, so any text can be put inside an
+// element or an attribute value. "&" has to go first: done later, it would also hit the
+// "&" of the entities already put in, and "<" would end up as "&amp;lt;".
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// Comments in a discussion. A reply keeps the index of the comment it answers in parent;
+// top-level comments have no parent.
+pub struct Comment {
+    pub author: String,
+    pub body: String,
+    pub parent: Option<usize>,
+}
+
+impl Summary for Comment {
+    fn summarize(&self) -> String {
+        format!("{}: {}", self.author, self.body)
+    }
+}
+
+// Number of ancestors of the comment at index: 0 for a top-level comment, 1 for a direct
+// reply, and so on. An index outside the slice has depth 0, and a parent index outside
+// the slice ends the walk. The walk is capped at the number of comments so a cycle of
+// parents can't loop forever.
+pub fn thread_depth(comments: &[Comment], index: usize) -> usize {
+    let mut depth = 0;
+    let mut current = index;
+
+    while let Some(parent) = comments.get(current).and_then(|c| c.parent) {
+        if parent >= comments.len() || depth == comments.len() {
+            break;
+        }
+        depth += 1;
+        current = parent;
+    }
+
+    depth
+}
+
+// Timelines
+// Anything that knows when it was posted can be put in order. sort_by_recency takes a
+// slice, so a &mut Vec<T> works too, and puts the newest item first. The sort is stable:
+// items with the same timestamp keep their order.
+pub trait Timestamped {
+    fn timestamp(&self) -> u64;
+}
+
+impl Timestamped for Tweet {
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+impl Timestamped for NewsArticle {
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+pub fn sort_by_recency<T: Summary + Timestamped>(items: &mut [T]) {
+    items.sort_by_key(|item| Reverse(item.timestamp()));
 }
 
 // Default implementations can call other methods in the same trait
@@ -73,6 +376,11 @@ pub trait SummaryMethods {
     fn summarize_call_method(&self) -> String {
         return format!("(Read more from {}...)", self.summarize_author());
     }
+
+    // split_whitespace never yields empty tokens, even with repeated spaces
+    fn summary_word_count(&self) -> usize {
+        self.summarize_call_method().split_whitespace().count()
+    }
 }
 
 impl SummaryMethods for Tweet {
@@ -81,6 +389,12 @@ impl SummaryMethods for Tweet {
     }
 }
 
+impl SummaryMethods for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+}
+
 // Traits as parameters
 // The input parameter accepts any type that implements the specified trait.
 // We can call notify and pass in any instance of NewsArticle or Tweet. Code that calls the function with any other type, such as a String or an i32, won’t compile because those types don’t implement Summary.
@@ -88,10 +402,93 @@ pub fn notify(item: &impl Summary) {
     return println!("Breaking news! {}", item.summarize());
 }
 
+// Batch of breaking news, numbered from 1. The items are trait objects, so one batch can
+// mix articles and tweets. notify_batch prints to stdout; notify_batch_to writes to any
+// io::Write, such as a Vec<u8> when we want to check the output.
+pub fn notify_batch(items: &[&dyn Summary]) {
+    notify_batch_to(items, &mut io::stdout()).expect("writing to stdout should not fail");
+}
+
+pub fn notify_batch_to<W: Write>(items: &[&dyn Summary], out: &mut W) -> io::Result<()> {
+    for (index, item) in items.iter().enumerate() {
+        writeln!(out, "{}. {}", index + 1, item.summarize())?;
+    }
+    Ok(())
+}
+
 // Trait bounds
 // To enforce both items have the samve type
 pub fn notify_two<T: Summary>(item1: &T, item2: &T) {}
 
+// With one generic parameter per argument, each one can be a different type as long as
+// both implement Summary, so we can pass a NewsArticle and a Tweet together.
+pub fn notify_pair<A: Summary, B: Summary>(a: &A, b: &B) -> String {
+    format!("{} | {}", a.summarize(), b.summarize())
+}
+
+// Aggregates over a slice of summarizable items
+// Lengths are counted in chars, not bytes, so accented letters or emoji count once.
+pub fn total_summary_chars<T: Summary>(items: &[T]) -> usize {
+    items
+        .iter()
+        .map(|item| item.summarize().chars().count())
+        .sum()
+}
+
+pub fn feed_is_empty<T: Summary>(items: &[T]) -> bool {
+    items.is_empty()
+}
+
+// Mixed feeds
+// An enum lists every kind of item a feed can hold, so matching on it is exhaustive.
+// A trait object instead accepts any type that implements Summary, including ones this
+// crate doesn't know about. Converting a FeedItem into a Box<dyn Summary> lets code built
+// around the enum hand its items to code built around trait objects.
+pub enum FeedItem {
+    Article(NewsArticle),
+    Tweet(Tweet),
+}
+
+impl From<FeedItem> for Box<dyn Summary> {
+    fn from(item: FeedItem) -> Box<dyn Summary> {
+        match item {
+            FeedItem::Article(article) => Box::new(article),
+            FeedItem::Tweet(tweet) => Box::new(tweet),
+        }
+    }
+}
+
+pub fn into_boxed_feed(items: Vec<FeedItem>) -> Vec<Box<dyn Summary>> {
+    items.into_iter().map(Box::from).collect()
+}
+
+// Each item is summarized through dynamic dispatch, in order
+pub fn render_boxed(items: &[Box<dyn Summary>]) -> Vec<String> {
+    items.iter().map(|item| item.summarize()).collect()
+}
+
+// A feed of any mix of summarizable items, kept in the order they were added.
+// Summary can be used as a trait object because summarize takes &self and doesn't
+// mention Self or generic parameters in its signature.
+#[derive(Default)]
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed::default()
+    }
+
+    pub fn add(&mut self, item: Box<dyn Summary>) {
+        self.items.push(item);
+    }
+
+    pub fn render_all(&self) -> Vec<String> {
+        render_boxed(&self.items)
+    }
+}
+
 // Specifying multiple trait bounds with +
 pub fn notify_multiple(item: &(impl Summary + Display)) {}
 pub fn notify_generic<T: Summary + Display>(item: &T) {}
@@ -121,6 +518,7 @@ fn returns_summarizable() -> impl Summary {
         content: String::from("of course, as you probably already know, people"),
         reply: false,
         retweet: false,
+        timestamp: 0,
     };
 }
 
@@ -148,3 +546,404 @@ impl<T: Display + PartialOrd> Pair<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article() -> NewsArticle {
+        NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from(
+                "The Pittsburgh Penguins once again are the best \
+                 hockey team in the NHL.",
+            ),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    fn tweet() -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn notify_pair_mixes_summary_types() {
+        assert_eq!(
+            notify_pair(&article(), &tweet()),
+            "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA) | \
+             horse_ebooks: of course, as you probably already know, people"
+        );
+    }
+
+    #[test]
+    fn builder_builds_a_valid_article() {
+        let article = NewsArticleBuilder::new()
+            .headline("Penguins win the Stanley Cup Championship!")
+            .location("Pittsburgh, PA, USA")
+            .author("Iceburgh")
+            .content("The Pittsburgh Penguins once again are the best hockey team in the NHL.")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            article.summarize(),
+            "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+        );
+        assert_eq!(
+            article.content,
+            "The Pittsburgh Penguins once again are the best hockey team in the NHL."
+        );
+    }
+
+    #[test]
+    fn builder_rejects_empty_headline() {
+        let result = NewsArticleBuilder::new().author("Iceburgh").build();
+
+        assert_eq!(
+            result.err(),
+            Some(String::from("a news article needs a headline"))
+        );
+    }
+
+    #[test]
+    fn builder_rejects_empty_author() {
+        let result = NewsArticleBuilder::new()
+            .headline("Penguins win the Stanley Cup Championship!")
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(String::from("a news article needs an author"))
+        );
+    }
+
+    fn comment(author: &str, body: &str, parent: Option<usize>) -> Comment {
+        Comment {
+            author: String::from(author),
+            body: String::from(body),
+            parent,
+        }
+    }
+
+    #[test]
+    fn comment_summary_and_thread_depth() {
+        let comments = vec![
+            comment("ada", "Great match!", None),
+            comment("bob", "Agreed", Some(0)),
+            comment("cy", "Not really", Some(1)),
+            comment("dee", "Lost reply", Some(10)),
+        ];
+
+        assert_eq!(comments[0].summarize(), "ada: Great match!");
+        assert_eq!(thread_depth(&comments, 0), 0);
+        assert_eq!(thread_depth(&comments, 2), 2);
+        assert_eq!(thread_depth(&comments, 3), 0);
+        assert_eq!(thread_depth(&comments, 42), 0);
+    }
+
+    #[test]
+    fn feed_totals_for_empty_slice() {
+        let tweets: Vec<Tweet> = Vec::new();
+
+        assert_eq!(total_summary_chars(&tweets), 0);
+        assert!(feed_is_empty(&tweets));
+    }
+
+    #[test]
+    fn feed_totals_for_populated_slice() {
+        let mut other = tweet();
+        other.username = String::from("zoë");
+        other.content = String::from("hi");
+        let tweets = vec![tweet(), other];
+
+        // "horse_ebooks: " is 14 chars plus 47 of content, "zoë: hi" is 7 chars
+        assert_eq!(total_summary_chars(&tweets), 61 + 7);
+        assert!(!feed_is_empty(&tweets));
+    }
+
+    #[test]
+    fn feed_items_convert_to_boxed_summaries() {
+        let items = vec![FeedItem::Tweet(tweet()), FeedItem::Article(article())];
+
+        let boxed = into_boxed_feed(items);
+
+        assert_eq!(
+            render_boxed(&boxed),
+            vec![
+                String::from("horse_ebooks: of course, as you probably already know, people"),
+                String::from(
+                    "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn summarize_truncated_cuts_on_char_boundary() {
+        let mut crab = tweet();
+        crab.username = String::from("ferris");
+        crab.content = String::from("🦀🦀🦀 rocks");
+
+        // Byte 10 falls inside the first crab, but 10 chars end after the second one
+        assert_eq!(crab.summarize_truncated(10), "ferris: 🦀🦀…");
+        assert_eq!(crab.summarize_truncated(100), "ferris: 🦀🦀🦀 rocks");
+    }
+
+    #[test]
+    fn wrap_content_breaks_at_whitespace() {
+        assert_eq!(
+            article().wrap_content(20),
+            vec![
+                "The Pittsburgh",
+                "Penguins once again",
+                "are the best hockey",
+                "team in the NHL.",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_content_puts_long_word_on_its_own_line() {
+        let mut article = article();
+        article.content = String::from("a supercalifragilistic word");
+
+        assert_eq!(
+            article.wrap_content(10),
+            vec!["a", "supercalifragilistic", "word"]
+        );
+    }
+
+    #[test]
+    fn wrap_content_of_empty_content() {
+        let mut article = article();
+        article.content = String::new();
+
+        assert_eq!(article.wrap_content(10), Vec::<String>::new());
+    }
+
+    #[test]
+    fn feed_renders_mixed_items_in_order() {
+        let mut feed = Feed::new();
+        feed.add(Box::new(tweet()));
+        feed.add(Box::new(article()));
+
+        let rendered = feed.render_all();
+
+        assert_eq!(rendered.len(), 2);
+        assert_eq!(
+            rendered[0],
+            "horse_ebooks: of course, as you probably already know, people"
+        );
+        assert_eq!(
+            rendered[1],
+            "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+        );
+    }
+
+    #[test]
+    fn tweet_hashtags() {
+        let mut tweet = tweet();
+        tweet.content = String::from("Loving #Rust and #WebAssembly, again! #");
+
+        assert_eq!(tweet.hashtags(), vec!["rust", "webassembly"]);
+        assert_eq!(tweet.mentions(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn tweet_mentions() {
+        let mut tweet = tweet();
+        tweet.content = String::from("@Ferris thanks! cc @horse_ebooks, mail me@example.com");
+
+        assert_eq!(tweet.mentions(), vec!["ferris", "horse_ebooks"]);
+    }
+
+    #[test]
+    fn notify_batch_numbers_items_in_order() {
+        let article = article();
+        let tweet = tweet();
+        let mut out: Vec<u8> = Vec::new();
+
+        notify_batch_to(&[&tweet, &article], &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1. horse_ebooks: of course, as you probably already know, people\n\
+             2. Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)\n"
+        );
+    }
+
+    #[test]
+    fn summary_word_count_for_article_and_tweet() {
+        let mut article = article();
+        article.author = String::from("Mario Lemieux");
+
+        // "(Read more from Mario Lemieux...)"
+        assert_eq!(article.summary_word_count(), 5);
+        // "(Read more from @horse_ebooks...)"
+        assert_eq!(tweet().summary_word_count(), 4);
+    }
+
+    #[test]
+    fn sort_by_recency_puts_newest_first() {
+        let posted_at = |content: &str, timestamp| {
+            let mut tweet = tweet();
+            tweet.content = String::from(content);
+            tweet.timestamp = timestamp;
+            tweet
+        };
+        let mut tweets = vec![
+            posted_at("second", 200),
+            posted_at("first", 100),
+            posted_at("third", 300),
+        ];
+
+        sort_by_recency(&mut tweets);
+
+        let order: Vec<&str> = tweets.iter().map(|t| t.content.as_str()).collect();
+        assert_eq!(order, vec!["third", "second", "first"]);
+    }
+
+    #[test]
+    fn escape_html_each_special_character() {
+        assert_eq!(escape_html("&"), "&amp;");
+        assert_eq!(escape_html("<"), "&lt;");
+        assert_eq!(escape_html(">"), "&gt;");
+        assert_eq!(escape_html("\""), "&quot;");
+        assert_eq!(escape_html("'"), "&#39;");
+        assert_eq!(escape_html("no markup"), "no markup");
+    }
+
+    #[test]
+    fn escape_html_mixed_text() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        // Already escaped text gets escaped again rather than left alone
+        assert_eq!(escape_html("&lt;"), "&amp;lt;");
+    }
+
+    fn with_content(content: &str) -> NewsArticle {
+        let mut article = article();
+        article.content = String::from(content);
+        article
+    }
+
+    #[test]
+    fn content_plain_strips_each_construct() {
+        assert_eq!(
+            with_content("the **best** team").content_plain(),
+            "the best team"
+        );
+        assert_eq!(
+            with_content("once *again* champions").content_plain(),
+            "once again champions"
+        );
+        assert_eq!(
+            with_content("run `cargo test` first").content_plain(),
+            "run cargo test first"
+        );
+        assert_eq!(
+            with_content("see [the NHL](https://www.nhl.com) site").content_plain(),
+            "see the NHL site"
+        );
+        assert_eq!(
+            with_content("**[Penguins](https://example.com)** win").content_plain(),
+            "Penguins win"
+        );
+    }
+
+    #[test]
+    fn content_plain_leaves_plain_text_and_unmatched_markers() {
+        assert_eq!(
+            article().content_plain(),
+            "The Pittsburgh Penguins once again are the best hockey team in the NHL."
+        );
+        for content in ["2 * 3", "**open", "a `tick", "[link](https://"] {
+            assert_eq!(with_content(content).content_plain(), content);
+        }
+    }
+
+    #[test]
+    fn summarize_into_matches_summarize() {
+        let article = article();
+        let tweet = tweet();
+        let mut buf = String::new();
+
+        // NewsArticle uses the default, Tweet its own override
+        article.summarize_into(&mut buf);
+        assert_eq!(buf, article.summarize());
+
+        buf.clear();
+        tweet.summarize_into(&mut buf);
+        assert_eq!(buf, tweet.summarize());
+
+        // The summary is appended, not written over what is already there
+        tweet.summarize_into(&mut buf);
+        assert_eq!(buf, tweet.summarize().repeat(2));
+    }
+
+    #[test]
+    fn summarize_truncated_with_word_boundary() {
+        // "horse_ebooks: of course, as you probably already know, people"
+        let tweet = tweet();
+
+        assert_eq!(
+            tweet.summarize_truncated_with(30, "...", true),
+            "horse_ebooks: of course, as..."
+        );
+        assert_eq!(
+            tweet.summarize_truncated_with(100, "...", true),
+            tweet.summarize()
+        );
+    }
+
+    #[test]
+    fn summarize_truncated_with_mid_word() {
+        let tweet = tweet();
+
+        let truncated = tweet.summarize_truncated_with(30, "...", false);
+        assert_eq!(truncated, "horse_ebooks: of course, as...");
+        assert_eq!(truncated.chars().count(), 30);
+
+        let truncated = tweet.summarize_truncated_with(33, "...", false);
+        assert_eq!(truncated, "horse_ebooks: of course, as yo...");
+        assert_eq!(
+            tweet.summarize_truncated_with(33, "...", true),
+            "horse_ebooks: of course, as..."
+        );
+    }
+
+    #[test]
+    fn summarize_truncated_with_custom_ellipsis() {
+        let tweet = tweet();
+
+        let truncated = tweet.summarize_truncated_with(20, " [more]", true);
+        assert_eq!(truncated, "horse_ebooks: [more]");
+        assert!(truncated.chars().count() <= 20);
+        assert_eq!(
+            tweet.summarize_truncated_with(20, "…", false),
+            "horse_ebooks: of co…"
+        );
+    }
+
+    #[test]
+    fn summarize_truncated_with_ellipsis_longer_than_limit() {
+        let tweet = tweet();
+
+        let truncated = tweet.summarize_truncated_with(2, "...", false);
+        assert!(truncated.chars().count() <= 2);
+        assert_eq!(truncated, "ho");
+
+        assert_eq!(tweet.summarize_truncated_with(3, "...", true), "hor");
+        assert_eq!(tweet.summarize_truncated_with(0, "…", false), "");
+    }
+}
@@ -1,6 +1,8 @@
 // Here’s an example of how a binary crate could use our aggregator library crate:
 
-use aggregator::{notify, NewsArticle, Summary, SummaryDefault, SummaryMethods, Tweet};
+use aggregator::{
+    notify, notify_batch, NewsArticle, Summary, SummaryDefault, SummaryMethods, Tweet,
+};
 
 fn main() {
     let tweet = Tweet {
@@ -8,6 +10,7 @@ fn main() {
         content: String::from("of course, as you probably already know, people"),
         reply: false,
         retweet: false,
+        timestamp: 1_700_000_000,
     };
 
     println!("1 new tweet: {}", tweet.summarize());
@@ -21,6 +24,7 @@ fn main() {
             "The Pittsburgh Penguins once again are the best \
              hockey team in the NHL.",
         ),
+        timestamp: 1_700_000_000,
     };
 
     println!("New article available! {}", article.summarize_default());
@@ -31,10 +35,12 @@ fn main() {
         content: String::from("of course, as you probably already know, people"),
         reply: false,
         retweet: false,
+        timestamp: 1_700_000_000,
     };
 
     println!("1 new tweet: {}", tweet.summarize_call_method());
 
     notify(&article);
     notify(&tweet);
+    notify_batch(&[&article, &tweet]);
 }
@@ -97,6 +97,9 @@ fn largest<T: std::cmp::PartialOrd>(list: &[T]) -> &T {
     return largest;
 }
 
+// Returning the largest value itself instead of a reference: see largest_copy and
+// largest_clone in lib.rs.
+
 // Struct with one generic type
 struct Point<T> {
     x: T,
@@ -163,3 +166,5 @@ impl<X1, Y1> Point3<X1, Y1> {
         };
     }
 }
+
+// The same idea with the T and U names of the struct definition: see MixedPoint in lib.rs.
@@ -0,0 +1,154 @@
+// The files next to this one are standalone examples, each with its own main. The code
+// here is part of the crate, so cargo builds and tests it.
+
+// Returning T instead of &T means we have to get a value out of the slice, and how we do
+// that depends on the trait bound we pick.
+// With Copy, values are duplicated bit by bit, which is cheap, but only works for types
+// stored entirely on the stack, like i32 or char (a String can't be Copy).
+// With Clone, any type that can be cloned works, including String, but cloning may
+// allocate on the heap. That's why largest_clone keeps a reference while scanning the
+// slice and clones only once at the end.
+// Both return None for an empty slice instead of panicking.
+pub fn largest_copy<T: PartialOrd + Copy>(list: &[T]) -> Option<T> {
+    let mut largest = *list.first()?;
+
+    for &item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+
+    Some(largest)
+}
+
+pub fn largest_clone<T: PartialOrd + Clone>(list: &[T]) -> Option<T> {
+    let mut largest = list.first()?;
+
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+
+    Some(largest.clone())
+}
+
+// The same idea as Point3::mixup in generic-types.rs, with the T and U names of the
+// struct definition: x and y can hold different types, and mixup takes a point with two
+// other types V and W, returning a point with this x (type T) and the other's y (type W).
+pub struct MixedPoint<T, U> {
+    pub x: T,
+    pub y: U,
+}
+
+impl<T, U> MixedPoint<T, U> {
+    pub fn mixup<V, W>(self, other: MixedPoint<V, W>) -> MixedPoint<T, W> {
+        MixedPoint {
+            x: self.x,
+            y: other.y,
+        }
+    }
+}
+
+/*
+Same relationship as longest in lifetimes.rs: the returned slice lives as long as both
+inputs, while the usize is a plain value with no lifetime attached. Lengths are counted in
+chars rather than bytes, so the comparison is done on chars too. On a tie y is returned,
+like longest does.
+*/
+pub fn longest_with_len<'a>(x: &'a str, y: &'a str) -> (&'a str, usize) {
+    let x_len = x.chars().count();
+    let y_len = y.chars().count();
+
+    if x_len > y_len {
+        (x, x_len)
+    } else {
+        (y, y_len)
+    }
+}
+
+// The struct from lifetimes.rs, holding a reference, so it can't outlive the text the part
+// is borrowed from.
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    /*
+    Two struct borrows with explicit lifetimes:
+    With two references as input, the third elision rule would give the return type the
+    lifetime of &self, but we may return other's part, so we tie both borrows and the
+    return value to the same lifetime. 'a is already taken by the impl block, so the
+    method declares its own 'b. On a tie, self's part is returned.
+    */
+    pub fn longer_part<'b>(&'b self, other: &'b ImportantExcerpt) -> &'b str {
+        if other.part.len() > self.part.len() {
+            other.part
+        } else {
+            self.part
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_copy_of_integers() {
+        assert_eq!(largest_copy(&[34, 50, 25, 100, 65]), Some(100));
+        assert_eq!(largest_copy::<i32>(&[]), None);
+    }
+
+    #[test]
+    fn largest_clone_of_strings() {
+        let words = vec![
+            String::from("apple"),
+            String::from("pear"),
+            String::from("banana"),
+        ];
+
+        assert_eq!(largest_clone(&words), Some(String::from("pear")));
+        assert_eq!(largest_clone::<String>(&[]), None);
+    }
+
+    #[test]
+    fn mixup_takes_x_from_self_and_y_from_other() {
+        let p1 = MixedPoint { x: 5, y: 10.4 };
+        let p2 = MixedPoint { x: 'c', y: "Hello" };
+
+        // The annotation checks the resulting types: i32 from p1 and &str from p2
+        let p3: MixedPoint<i32, &str> = p1.mixup(p2);
+
+        assert_eq!(p3.x, 5);
+        assert_eq!(p3.y, "Hello");
+    }
+
+    #[test]
+    fn longest_with_len_x_longer() {
+        assert_eq!(longest_with_len("abcd", "xyz"), ("abcd", 4));
+    }
+
+    #[test]
+    fn longest_with_len_y_longer() {
+        assert_eq!(longest_with_len("xyz", "héllo"), ("héllo", 5));
+    }
+
+    #[test]
+    fn longest_with_len_tie_returns_y() {
+        assert_eq!(longest_with_len("abc", "xyz"), ("xyz", 3));
+    }
+
+    #[test]
+    fn longer_part_of_two_excerpts() {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        let first_sentence = novel.split('.').next().unwrap();
+        let long = ImportantExcerpt {
+            part: first_sentence,
+        };
+        let short = ImportantExcerpt { part: "Call me" };
+
+        assert_eq!(long.longer_part(&short), "Call me Ishmael");
+        assert_eq!(short.longer_part(&long), "Call me Ishmael");
+    }
+}
@@ -150,6 +150,8 @@ fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
     }
 }
 
+// Returning the slice together with its length: see longest_with_len in lib.rs.
+
 /*
 Thinking in terms of Lifetimes
 Returning always the first parameter in the longest function, we can just specify the
@@ -202,6 +204,8 @@ impl<'a> ImportantExcerpt<'a> {
         println!("Attention please: {}", announcement);
         self.part
     }
+
+    // Comparing two excerpts: see ImportantExcerpt::longer_part in lib.rs.
 }
 
 // Generic Type Parameters, Trait Bounds, and Lifetimes Together
@@ -1 +1,28 @@
+use generics::{largest_clone, largest_copy, longest_with_len, ImportantExcerpt, MixedPoint};
 
+fn main() {
+    // Returning the largest value itself instead of a reference
+    let number_list = vec![34, 50, 25, 100, 65];
+    println!("The largest number is {:?}", largest_copy(&number_list));
+    let word_list = vec![String::from("apple"), String::from("pear")];
+    println!("The largest word is {:?}", largest_clone(&word_list));
+
+    // Mixing the types of two points
+    let p1 = MixedPoint { x: 5, y: 10.4 };
+    let p2 = MixedPoint { x: 'c', y: "Hello" };
+    let p3 = p1.mixup(p2);
+    println!("p3.x = {}, p3.y = {}", p3.x, p3.y);
+
+    // Returning the slice together with its length
+    let (result, len) = longest_with_len("long string is long", "xyz");
+    println!("The longest string is: {} ({} chars)", result, len);
+
+    // Comparing two excerpts borrowed from different strings
+    let i = ImportantExcerpt {
+        part: "Call me Ishmael",
+    };
+    let j = ImportantExcerpt {
+        part: "Some years ago",
+    };
+    println!("The longer part is: {}", i.longer_part(&j));
+}
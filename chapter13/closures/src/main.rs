@@ -29,12 +29,14 @@ Functions can also implement these three traits.
 
 */
 
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
 use std::{thread, time::Duration};
 
 fn main() {
-    let store = Inventory {
-        shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue],
-    };
+    let mut store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
 
     let user_pref1 = Some(ShirtColor::Red);
     let giveaway1 = store.giveaway(user_pref1);
@@ -49,6 +51,18 @@ fn main() {
         "The user with preference {:?} gets {:?}",
         user_pref2, giveaway2
     );
+    println!("Giveaways so far: {:?}", store.giveaway_log());
+    println!("Most stocked: {}", store.most_stocked());
+    for (color, count) in store.report() {
+        println!("{color}: {count}");
+    }
+
+    store.restock(ShirtColor::Red, 3);
+    println!(
+        "After restocking: {} shirts, most stocked is {}",
+        store.total(),
+        store.most_stocked()
+    );
 
     // Storing a closure as a variable
     let _expensive_closure = |num: u32| -> u32 {
@@ -137,22 +151,89 @@ fn main() {
         r.width // returns the width
     });
     println!("{:#?}, sorted in {num_sort_operations} operations", list);
+
+    // Retrying with backoff: the closure mutates calls, so it's FnMut
+    let mut calls = 0;
+    let (result, delays) = retry_backoff(
+        || {
+            calls += 1;
+            if calls < 3 {
+                Err("service unavailable")
+            } else {
+                Ok(calls)
+            }
+        },
+        5,
+        Duration::from_millis(100),
+    );
+    println!("{:?} after waiting {:?}", result, delays);
+
+    // Caching a closure's results per input
+    let mut word_length = Cacher::new(|word: &String| {
+        println!("measuring {word}...");
+        word.chars().count()
+    });
+    println!("{}", word_length.value(String::from("shirt")));
+    println!("{}", word_length.value(String::from("shirt")));
+
+    let mut halves = FallibleCacher::new(|num| {
+        if num % 2 == 0 {
+            Ok(num / 2)
+        } else {
+            Err(format!("{num} is odd"))
+        }
+    });
+    println!("{:?} {:?}", halves.value(10), halves.value(7));
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+// The derived order follows the declaration order of the variants, so Red < Blue. That
+// is enough to keep colors sorted, for example as keys of a BTreeMap.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 enum ShirtColor {
     Red,
     Blue,
 }
 
+impl ShirtColor {
+    // Every color, in order
+    const ALL: [ShirtColor; 2] = [ShirtColor::Red, ShirtColor::Blue];
+}
+
+impl fmt::Display for ShirtColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ShirtColor::Red => "Red",
+            ShirtColor::Blue => "Blue",
+        };
+        write!(f, "{name}")
+    }
+}
+
 struct Inventory {
     shirts: Vec<ShirtColor>,
+    // Every giveaway so far: the preference it was asked with and the color given.
+    // giveaway only borrows self immutably, so the log needs interior mutability.
+    giveaways: RefCell<Vec<(Option<ShirtColor>, ShirtColor)>>,
 }
 
 impl Inventory {
+    fn new(shirts: Vec<ShirtColor>) -> Inventory {
+        Inventory {
+            shirts,
+            giveaways: RefCell::new(Vec::new()),
+        }
+    }
+
     fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
         // closure expression (anonymous func): || self.most_stocked()
-        return user_preference.unwrap_or_else(|| self.most_stocked());
+        let color = user_preference.unwrap_or_else(|| self.most_stocked());
+        self.giveaways.borrow_mut().push((user_preference, color));
+        color
+    }
+
+    // A copy of the log, oldest giveaway first
+    fn giveaway_log(&self) -> Vec<(Option<ShirtColor>, ShirtColor)> {
+        self.giveaways.borrow().clone()
     }
 
     fn most_stocked(&self) -> ShirtColor {
@@ -171,6 +252,25 @@ impl Inventory {
             return ShirtColor::Blue;
         }
     }
+
+    fn restock(&mut self, color: ShirtColor, quantity: usize) {
+        self.shirts.extend(std::iter::repeat_n(color, quantity));
+    }
+
+    fn total(&self) -> usize {
+        self.shirts.len()
+    }
+
+    // Count of every color, sorted by color. Colors that are out of stock are listed too,
+    // with a count of 0, so the report always has one entry per color.
+    fn report(&self) -> Vec<(ShirtColor, usize)> {
+        let mut counts: BTreeMap<ShirtColor, usize> =
+            ShirtColor::ALL.iter().map(|color| (*color, 0)).collect();
+        for color in &self.shirts {
+            *counts.entry(*color).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
 }
 
 fn _add_one_v1(x: u32) -> u32 {
@@ -182,3 +282,236 @@ struct Rectangle {
     width: u32,
     height: u32,
 }
+
+// Calls op until it succeeds or it has been called attempts times (at least once, since
+// there has to be a result to return). Between failures the delay doubles, starting at
+// base, and stops growing at Duration::MAX instead of overflowing. Nothing actually
+// sleeps: the delays are returned so the caller can decide how to wait, which also keeps
+// tests fast.
+fn retry_backoff<T, E, F: FnMut() -> Result<T, E>>(
+    mut op: F,
+    attempts: usize,
+    base: Duration,
+) -> (Result<T, E>, Vec<Duration>) {
+    let mut delays = Vec::new();
+    let mut delay = base;
+
+    loop {
+        let result = op();
+        if result.is_ok() || delays.len() + 1 >= attempts {
+            return (result, delays);
+        }
+        delays.push(delay);
+        delay = delay.saturating_mul(2);
+    }
+}
+
+// Memoization: a struct holding a closure and the results it has already produced. The
+// closure only runs the first time a key is asked for; after that the stored value is
+// cloned out of the map. The key is cloned too, because the map keeps its own copy.
+struct Cacher<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&K) -> V,
+{
+    calculation: F,
+    values: HashMap<K, V>,
+}
+
+impl<K, V, F> Cacher<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: Fn(&K) -> V,
+{
+    fn new(calculation: F) -> Cacher<K, V, F> {
+        Cacher {
+            calculation,
+            values: HashMap::new(),
+        }
+    }
+
+    fn value(&mut self, key: K) -> V {
+        match self.values.get(&key) {
+            Some(v) => v.clone(),
+            None => {
+                let v = (self.calculation)(&key);
+                self.values.insert(key, v.clone());
+                v
+            }
+        }
+    }
+}
+
+// The same idea for a calculation that can fail. Only successes are stored: an Err might
+// be temporary, so asking for that key again calls the closure again.
+struct FallibleCacher<F>
+where
+    F: Fn(u32) -> Result<u32, String>,
+{
+    calculation: F,
+    values: HashMap<u32, u32>,
+}
+
+impl<F> FallibleCacher<F>
+where
+    F: Fn(u32) -> Result<u32, String>,
+{
+    fn new(calculation: F) -> FallibleCacher<F> {
+        FallibleCacher {
+            calculation,
+            values: HashMap::new(),
+        }
+    }
+
+    fn value(&mut self, key: u32) -> Result<u32, String> {
+        if let Some(v) = self.values.get(&key) {
+            return Ok(*v);
+        }
+        let v = (self.calculation)(key)?;
+        self.values.insert(key, v);
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff_doubles_the_delay() {
+        let base = Duration::from_millis(50);
+        let mut failures_left = 2;
+
+        let (result, delays) = retry_backoff(
+            || {
+                if failures_left > 0 {
+                    failures_left -= 1;
+                    Err("try again")
+                } else {
+                    Ok("done")
+                }
+            },
+            5,
+            base,
+        );
+
+        assert_eq!(result, Ok("done"));
+        assert_eq!(delays, vec![base, base * 2]);
+    }
+
+    #[test]
+    fn retry_backoff_gives_up_after_attempts() {
+        let mut calls = 0;
+
+        let (result, delays) = retry_backoff(
+            || -> Result<(), &str> {
+                calls += 1;
+                Err("down")
+            },
+            3,
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(result, Err("down"));
+        assert_eq!(calls, 3);
+        assert_eq!(delays.len(), 2);
+    }
+
+    #[test]
+    fn retry_backoff_delay_saturates() {
+        let (result, delays) =
+            retry_backoff(|| -> Result<(), &str> { Err("down") }, 3, Duration::MAX);
+
+        assert_eq!(result, Err("down"));
+        assert_eq!(delays, vec![Duration::MAX, Duration::MAX]);
+    }
+
+    #[test]
+    fn cacher_calls_the_closure_once_per_key() {
+        let calls = std::cell::Cell::new(0);
+        let mut cacher = Cacher::new(|word: &String| {
+            calls.set(calls.get() + 1);
+            word.to_uppercase()
+        });
+
+        assert_eq!(cacher.value(String::from("red")), "RED");
+        assert_eq!(cacher.value(String::from("red")), "RED");
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(cacher.value(String::from("blue")), "BLUE");
+        assert_eq!(cacher.value(String::from("red")), "RED");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn fallible_cacher_retries_errors_and_caches_successes() {
+        let calls = std::cell::Cell::new(0);
+        let mut cacher = FallibleCacher::new(|num| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                Err(String::from("timed out"))
+            } else {
+                Ok(num * 2)
+            }
+        });
+
+        assert_eq!(cacher.value(21), Err(String::from("timed out")));
+        assert_eq!(cacher.value(21), Ok(42));
+        assert_eq!(cacher.value(21), Ok(42));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn shirt_colors_print_and_sort() {
+        assert_eq!(ShirtColor::Red.to_string(), "Red");
+        assert_eq!(ShirtColor::Blue.to_string(), "Blue");
+
+        let mut colors = vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue];
+        colors.sort();
+        assert_eq!(
+            colors,
+            vec![ShirtColor::Red, ShirtColor::Blue, ShirtColor::Blue]
+        );
+    }
+
+    #[test]
+    fn report_lists_out_of_stock_colors() {
+        let store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Blue]);
+
+        assert_eq!(
+            store.report(),
+            vec![(ShirtColor::Red, 0), (ShirtColor::Blue, 2)]
+        );
+    }
+
+    #[test]
+    fn restock_changes_most_stocked() {
+        let mut store = Inventory::new(vec![ShirtColor::Red, ShirtColor::Red, ShirtColor::Blue]);
+        assert_eq!(store.most_stocked(), ShirtColor::Red);
+
+        store.restock(ShirtColor::Blue, 2);
+
+        assert_eq!(store.total(), 5);
+        assert_eq!(store.most_stocked(), ShirtColor::Blue);
+    }
+
+    #[test]
+    fn giveaway_log_records_each_decision() {
+        let store = Inventory::new(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
+
+        store.giveaway(Some(ShirtColor::Red));
+        store.giveaway(None);
+        store.giveaway(Some(ShirtColor::Blue));
+
+        assert_eq!(
+            store.giveaway_log(),
+            vec![
+                (Some(ShirtColor::Red), ShirtColor::Red),
+                (None, ShirtColor::Blue),
+                (Some(ShirtColor::Blue), ShirtColor::Blue),
+            ]
+        );
+    }
+}
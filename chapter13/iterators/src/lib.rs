@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[test]
 fn iterator_demonstration() {
     let v1 = vec![1, 2, 3];
@@ -61,6 +63,113 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn sums_shoe_counts_by_style_and_size() {
+        let shoes = vec![
+            Shoe {
+                size: 10,
+                style: String::from("sneaker"),
+            },
+            Shoe {
+                size: 13,
+                style: String::from("sandal"),
+            },
+            Shoe {
+                size: 10,
+                style: String::from("sneaker"),
+            },
+            Shoe {
+                size: 10,
+                style: String::from("boot"),
+            },
+        ];
+
+        let by_style = sum_by(&shoes, |s| s.style.clone(), |_| 1);
+        assert_eq!(by_style.len(), 3);
+        assert_eq!(by_style["sneaker"], 2);
+        assert_eq!(by_style["sandal"], 1);
+        assert_eq!(by_style["boot"], 1);
+
+        let by_size = sum_by(&shoes, |s| s.size, |_| 1);
+        assert_eq!(by_size, HashMap::from([(10, 3), (13, 1)]));
+    }
+
+    #[test]
+    fn window_max_typical_window() {
+        assert_eq!(window_max(&[1, 3, 2, 5, 4], 2), vec![3, 3, 5, 5]);
+    }
+
+    #[test]
+    fn window_max_window_as_long_as_slice() {
+        assert_eq!(window_max(&[1, 3, 2], 3), vec![3]);
+    }
+
+    #[test]
+    fn window_max_empty_edge_cases() {
+        assert_eq!(window_max(&[1, 3, 2], 0), Vec::<i32>::new());
+        assert_eq!(window_max(&[1, 3, 2], 4), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn chunked_sums_evenly_divisible() {
+        assert_eq!(chunked_sums(&[1, 2, 3, 4, 5, 6], 2), vec![3, 7, 11]);
+    }
+
+    #[test]
+    fn chunked_sums_uneven_last_chunk() {
+        assert_eq!(chunked_sums(&[1, 2, 3, 4, 5], 2), vec![3, 7, 5]);
+    }
+
+    #[test]
+    fn chunked_sums_zero_chunk() {
+        assert_eq!(chunked_sums(&[1, 2, 3], 0), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn parse_all_valid_tokens() {
+        assert_eq!(parse_all(&["1", "-2", "30"]), Ok(vec![1, -2, 30]));
+    }
+
+    #[test]
+    fn parse_all_stops_at_first_bad_token() {
+        assert_eq!(
+            parse_all(&["1", "two", "three"]),
+            Err(String::from("invalid integer: \"two\""))
+        );
+    }
+
+    #[test]
+    fn running_average_of_values() {
+        assert_eq!(running_average(&[2.0, 4.0, 6.0]), vec![2.0, 3.0, 4.0]);
+        assert_eq!(running_average(&[]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn running_extremes_widen() {
+        assert_eq!(
+            running_extremes(&[3, 1, 4, 1, 5]),
+            vec![(3, 3), (1, 3), (1, 4), (1, 4), (1, 5)]
+        );
+        assert_eq!(running_extremes(&[]), Vec::<(i32, i32)>::new());
+    }
+
+    #[test]
+    fn deltas_of_consecutive_pairs() {
+        assert_eq!(deltas(&[1, 4, 9, 7]), vec![3, 5, -2]);
+    }
+
+    #[test]
+    fn deltas_of_short_slices() {
+        assert_eq!(deltas(&[]), Vec::<i32>::new());
+        assert_eq!(deltas(&[5]), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn deltas_of_extreme_values() {
+        assert_eq!(deltas(&[i32::MIN, i32::MAX]), vec![i32::MAX]);
+        assert_eq!(deltas(&[i32::MAX, i32::MIN]), vec![i32::MIN]);
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -76,3 +185,98 @@ struct Shoe {
 fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
     shoes.into_iter().filter(|s| s.size == shoe_size).collect()
 }
+
+// scan is like fold, but it yields a value at every step instead of only the final one.
+// The state is the running (sum, count), and each step yields the mean so far, so element
+// i of the result is the mean of values[0..=i].
+pub fn running_average(values: &[f64]) -> Vec<f64> {
+    values
+        .iter()
+        .scan((0.0, 0), |(sum, count), value| {
+            *sum += value;
+            *count += 1;
+            Some(*sum / *count as f64)
+        })
+        .collect()
+}
+
+// Groups the items by the key closure and adds up the val closure within each group.
+// With val returning 1 for every item this is a histogram, like counting shoes per style.
+pub fn sum_by<T, K: Eq + std::hash::Hash, F: Fn(&T) -> K, G: Fn(&T) -> i32>(
+    items: &[T],
+    key: F,
+    val: G,
+) -> HashMap<K, i32> {
+    let mut sums = HashMap::new();
+
+    for item in items {
+        *sums.entry(key(item)).or_insert(0) += val(item);
+    }
+
+    sums
+}
+
+// windows yields every overlapping sub-slice of the given size, and each one is reduced to
+// its maximum. windows panics on a size of 0, so that case is handled first. A window
+// larger than the slice produces no windows at all.
+pub fn window_max(values: &[i32], window: usize) -> Vec<i32> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    values
+        .windows(window)
+        .map(|w| *w.iter().max().unwrap())
+        .collect()
+}
+
+// chunks splits the slice into consecutive, non-overlapping pieces; the last one is
+// shorter when the length isn't a multiple of the chunk size. Like windows, chunks panics
+// on a size of 0, so we return early instead.
+pub fn chunked_sums(values: &[i32], chunk: usize) -> Vec<i32> {
+    if chunk == 0 {
+        return Vec::new();
+    }
+
+    values.chunks(chunk).map(|c| c.iter().sum()).collect()
+}
+
+// Result implements FromIterator, so an iterator of Result<i32, String> can be collected
+// into a Result<Vec<i32>, String>: we get Ok with every value, or the first Err found.
+// collect stops pulling items at that first error, so the remaining tokens are not parsed.
+pub fn parse_all(tokens: &[&str]) -> Result<Vec<i32>, String> {
+    tokens
+        .iter()
+        .map(|token| {
+            token
+                .parse::<i32>()
+                .map_err(|_| format!("invalid integer: {:?}", token))
+        })
+        .collect()
+}
+
+// Same scan idea with (min, max) as the state. Starting from (i32::MAX, i32::MIN) means
+// the first value replaces both bounds, so there's no special case for it.
+pub fn running_extremes(values: &[i32]) -> Vec<(i32, i32)> {
+    values
+        .iter()
+        .scan((i32::MAX, i32::MIN), |(min, max), &value| {
+            *min = (*min).min(value);
+            *max = (*max).max(value);
+            Some((*min, *max))
+        })
+        .collect()
+}
+
+// Zipping the iterator with itself shifted by one pairs each value with the next one.
+// zip stops as soon as either side runs out, so slices of length 0 or 1 give no pairs.
+// The difference of two i32 can be outside of i32 (i32::MAX - i32::MIN), and a plain
+// subtraction would panic on it in debug builds. saturating_sub clamps such a difference
+// to i32::MAX or i32::MIN instead, so a huge jump still reads as a huge jump.
+pub fn deltas(values: &[i32]) -> Vec<i32> {
+    values
+        .iter()
+        .zip(values.iter().skip(1))
+        .map(|(current, next)| next.saturating_sub(*current))
+        .collect()
+}
@@ -1,11 +1,26 @@
 use std::env;
 use std::error::Error;
+use std::fmt;
 use std::fs;
+use std::io::{self, Read};
+use std::sync::mpsc;
 
+use hello_multithread::ThreadPool;
+
+#[derive(Clone)]
 pub struct Config {
     pub query: String,
-    pub file_path: String,
+    // Files to search, in the order given. "-" reads standard input instead.
+    pub file_paths: Vec<String>,
     pub ignore_case: bool,
+    pub with_filename: bool,
+    pub separator: String,
+    pub dry_run: bool,
+    pub stats: bool,
+    pub keep_crlf: bool,
+    // Lines of context to print before and after each match
+    pub before: usize,
+    pub after: usize,
 }
 
 impl Config {
@@ -17,65 +32,448 @@ impl Config {
         // Need to ignore it by going to the second item, which is the first argument.
         args.next();
 
+        let mut query = None;
+        let mut file_paths = Vec::new();
+        let mut with_filename = false;
+        let mut separator = String::from(":");
+        let mut dry_run = false;
+        let mut stats = false;
+        let mut keep_crlf = false;
+        let mut before = 0;
+        let mut after = 0;
+
+        // Options can go anywhere; the other arguments are the query and then one or more
+        // file paths.
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-H" | "--with-filename" => with_filename = true,
+                "--dry-run" => dry_run = true,
+                "--stats" => stats = true,
+                "--keep-crlf" => keep_crlf = true,
+                "-A" => after = context_lines(args.next())?,
+                "-B" => before = context_lines(args.next())?,
+                "-C" => {
+                    before = context_lines(args.next())?;
+                    after = before;
+                }
+                "--separator" => {
+                    separator = match args.next() {
+                        Some(sep) => sep,
+                        None => return Err("Didn't get a separator"),
+                    }
+                }
+                _ if query.is_none() => query = Some(arg),
+                _ => file_paths.push(arg),
+            }
+        }
+
         // First argument
-        let query = match args.next() {
+        let query = match query {
             Some(arg) => arg,
             None => return Err("Didn't get a query string"),
         };
 
-        // Second argument
-        let file_path = match args.next() {
-            Some(arg) => arg,
-            None => return Err("Didn't get a file path"),
-        };
+        // Second argument and on
+        if file_paths.is_empty() {
+            return Err("Didn't get a file path");
+        }
 
         let ignore_case = env::var("IGNORE_CASE").is_ok();
 
         return Ok(Config {
             query,
-            file_path,
+            file_paths,
             ignore_case,
+            with_filename,
+            separator,
+            dry_run,
+            stats,
+            keep_crlf,
+            before,
+            after,
         });
     }
+
+    // Turns on case-insensitive search no matter what IGNORE_CASE says, so code that
+    // builds a Config itself doesn't have to go through the environment.
+    pub fn with_case_insensitive(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    // The flags decide which matcher the search uses.
+    pub fn matcher(&self) -> Box<dyn Matcher> {
+        if self.ignore_case {
+            Box::new(CaseInsensitiveMatcher::new(&self.query))
+        } else {
+            Box::new(SubstringMatcher::new(&self.query))
+        }
+    }
+
+    // The filename is printed when asked for with -H, or when there is more than one
+    // source, since the lines could come from any of them.
+    pub fn show_filename(&self) -> bool {
+        self.with_filename || self.file_paths.len() > 1
+    }
+
+    // The separator goes after the filename, when it is printed, and after the line
+    // number. Standard input has no name, so it gets the one grep uses.
+    pub fn format_line(&self, path: &str, number: usize, line: &str) -> String {
+        let sep = &self.separator;
+        if self.show_filename() {
+            let name = if path == "-" {
+                "(standard input)"
+            } else {
+                path
+            };
+            format!("{name}{sep}{number}{sep}{line}")
+        } else {
+            format!("{number}{sep}{line}")
+        }
+    }
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
+// The value following -A, -B or -C
+fn context_lines(arg: Option<String>) -> Result<usize, &'static str> {
+    match arg {
+        Some(arg) => arg
+            .parse()
+            .map_err(|_| "Number of context lines must be a whole number"),
+        None => Err("Didn't get a number of context lines"),
+    }
+}
 
-    let results = if config.ignore_case {
-        search_case_insensitive(&config.query, &contents)
-    } else {
-        search(&config.query, &contents)
-    };
+// One key=value pair per line, always in the same order, so the output of --dry-run is
+// easy to read and to compare. Strings are quoted to make separators like a tab visible.
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "query={:?}", self.query)?;
+        writeln!(f, "file_paths={:?}", self.file_paths)?;
+        writeln!(f, "ignore_case={}", self.ignore_case)?;
+        writeln!(f, "with_filename={}", self.with_filename)?;
+        writeln!(f, "separator={:?}", self.separator)?;
+        writeln!(f, "dry_run={}", self.dry_run)?;
+        writeln!(f, "before={}", self.before)?;
+        writeln!(f, "after={}", self.after)?;
+        writeln!(f, "keep_crlf={}", self.keep_crlf)?;
+        write!(f, "stats={}", self.stats)
+    }
+}
+
+// Where run writes its lines. Printing to stdout is one implementation; collecting into a
+// Vec<String> is another, which lets tests check exactly what run produced.
+// Notes like the --stats footer go through emit_note, which by default is just another line.
+pub trait Output {
+    fn emit(&mut self, line: &str);
+
+    fn emit_note(&mut self, line: &str) {
+        self.emit(line);
+    }
+}
 
-    for line in results {
+pub struct StdoutOutput;
+
+// Notes go to stderr so they don't mix with the matches if stdout is piped.
+impl Output for StdoutOutput {
+    fn emit(&mut self, line: &str) {
         println!("{line}");
     }
 
+    fn emit_note(&mut self, line: &str) {
+        eprintln!("{line}");
+    }
+}
+
+impl Output for Vec<String> {
+    fn emit(&mut self, line: &str) {
+        self.push(String::from(line));
+    }
+}
+
+pub fn run(config: Config, output: &mut dyn Output) -> Result<(), Box<dyn Error>> {
+    // Show how the arguments were understood and stop before reading any file
+    if config.dry_run {
+        output.emit(&config.to_string());
+        return Ok(());
+    }
+
+    let matcher = config.matcher();
+    let with_context = config.before > 0 || config.after > 0;
+    let mut stats = Stats::default();
+    let mut groups_emitted = 0;
+
+    for path in &config.file_paths {
+        let contents = read_source(path)?;
+        let results = search_with(matcher.as_ref(), &contents);
+
+        // lines() drops both "\n" and "\r\n". Output implementations end each line with
+        // "\n" themselves, so putting the "\r" back is enough to re-emit a CRLF file's
+        // endings.
+        let restore_cr = config.keep_crlf && detect_line_ending(&contents) == "\r\n";
+
+        stats.tally(results.len());
+
+        // Without context every match is its own group. With context, groups are separated
+        // by "--" the way grep does it, also from the groups of the previous file.
+        let groups = if with_context {
+            search_with_context(matcher.as_ref(), &contents, config.before, config.after)
+        } else {
+            results.into_iter().map(|hit| vec![hit]).collect()
+        };
+
+        for group in groups {
+            if with_context && groups_emitted > 0 {
+                output.emit("--");
+            }
+            groups_emitted += 1;
+
+            for (number, line) in group {
+                let mut line = config.format_line(path, number, line);
+                if restore_cr {
+                    line.push('\r');
+                }
+                output.emit(&line);
+            }
+        }
+    }
+
+    if config.stats {
+        output.emit_note(&stats.to_string());
+    }
+
     Ok(())
 }
 
-pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+// "-" stands for standard input, as in most command-line tools.
+fn read_source(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+// A matching line found by run_parallel. file_index is the position of the file in
+// Config::file_paths and line_number starts at 1.
+#[derive(Debug, PartialEq)]
+pub struct SearchHit {
+    pub file_index: usize,
+    pub line_number: usize,
+    pub line: String,
+}
+
+// Searches every file of the config at the same time, one pool job per file. Each job sends
+// its hits back through a channel. Jobs finish in whatever order the threads run them, so
+// the hits are sorted by file and line at the end to keep the output the same every time.
+// A file that can't be read is reported on stderr and contributes no hits.
+pub fn run_parallel(config: &Config, pool: &ThreadPool) -> Vec<SearchHit> {
+    let (tx, rx) = mpsc::channel();
+
+    for (file_index, path) in config.file_paths.iter().enumerate() {
+        let tx = tx.clone();
+        let path = path.clone();
+        // Box<dyn Matcher> can't be sent to another thread, so each job builds its own
+        // from a copy of the config.
+        let config = config.clone();
+
+        pool.execute(move || {
+            let contents = match read_source(&path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    eprintln!("{path}: {e}");
+                    return;
+                }
+            };
+            let matcher = config.matcher();
+            let hits: Vec<SearchHit> = search_with(matcher.as_ref(), &contents)
+                .into_iter()
+                .map(|(line_number, line)| SearchHit {
+                    file_index,
+                    line_number,
+                    line: String::from(line),
+                })
+                .collect();
+            tx.send(hits).unwrap();
+        });
+    }
+
+    // Only the clones inside the jobs are left, so the loop below ends once all jobs are done
+    drop(tx);
+
+    let mut hits: Vec<SearchHit> = rx.iter().flatten().collect();
+    hits.sort_by_key(|hit| (hit.file_index, hit.line_number));
+    hits
+}
+
+// Running totals for the --stats footer: matches found and how many files had at least
+// one of them. Each searched file is added with tally.
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    pub matches: usize,
+    pub files: usize,
+}
+
+impl Stats {
+    pub fn tally(&mut self, matches: usize) {
+        self.matches += matches;
+        if matches > 0 {
+            self.files += 1;
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let matches = if self.matches == 1 {
+            "match"
+        } else {
+            "matches"
+        };
+        let files = if self.files == 1 { "file" } else { "files" };
+        write!(f, "{} {matches} in {} {files}", self.matches, self.files)
+    }
+}
+
+// The ending of the first line decides for the whole file. Content without any newline
+// gets the Unix "\n".
+pub fn detect_line_ending(contents: &str) -> &'static str {
+    match contents.find('\n') {
+        Some(i) if contents[..i].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+// The search functions return each matching line together with its line number, which
+// starts at 1 like in an editor.
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<(usize, &'a str)> {
     // We use the filter iterator adapter instead of the loop and if.
     return contents
         .lines()
-        .filter(|line| line.contains(query))
+        .enumerate()
+        .filter(|(_, line)| line.contains(query))
+        .map(|(i, line)| (i + 1, line))
         .collect(); // Finally we collect into an adaptor.
 }
 
-pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<(usize, &'a str)> {
     let query = query.to_lowercase();
     let mut results = Vec::new();
 
-    for line in contents.lines() {
+    for (i, line) in contents.lines().enumerate() {
         if line.to_lowercase().contains(&query) {
-            results.push(line);
+            results.push((i + 1, line));
         }
     }
 
     results
 }
 
+// Unicode-aware case-insensitive search. str::to_lowercase applies the Unicode lowercase
+// mapping, not just ASCII, so "STRASSE" matches "strasse" and "ÉCOLE" matches "école".
+// It allocates a new String for every line of contents (and one for the query), which is
+// the price of not being limited to ASCII.
+// Lowercasing is not full case folding: "ß" stays "ß", so "strasse" does not match
+// "Straße" even though they are the same word in German.
+pub fn search_case_insensitive_unicode<'a>(
+    query: &str,
+    contents: &'a str,
+) -> Vec<(usize, &'a str)> {
+    let query = query.to_lowercase();
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(&query))
+        .map(|(i, line)| (i + 1, line))
+        .collect()
+}
+
+// Matching a line is behind a trait so the search loop doesn't need to know how lines are
+// matched. Anyone can implement Matcher and hand it to search_with as a trait object.
+pub trait Matcher {
+    fn matches(&self, line: &str) -> bool;
+}
+
+pub struct SubstringMatcher {
+    query: String,
+}
+
+impl SubstringMatcher {
+    pub fn new(query: &str) -> SubstringMatcher {
+        SubstringMatcher {
+            query: String::from(query),
+        }
+    }
+}
+
+impl Matcher for SubstringMatcher {
+    fn matches(&self, line: &str) -> bool {
+        line.contains(&self.query)
+    }
+}
+
+// The query is lowercased once here instead of on every line.
+pub struct CaseInsensitiveMatcher {
+    query: String,
+}
+
+impl CaseInsensitiveMatcher {
+    pub fn new(query: &str) -> CaseInsensitiveMatcher {
+        CaseInsensitiveMatcher {
+            query: query.to_lowercase(),
+        }
+    }
+}
+
+impl Matcher for CaseInsensitiveMatcher {
+    fn matches(&self, line: &str) -> bool {
+        line.to_lowercase().contains(&self.query)
+    }
+}
+
+pub fn search_with<'a>(matcher: &dyn Matcher, contents: &'a str) -> Vec<(usize, &'a str)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| matcher.matches(line))
+        .map(|(i, line)| (i + 1, line))
+        .collect()
+}
+
+// Each match comes with up to before lines above it and after lines below it. When the
+// windows of two matches overlap or touch, they become one group, so no line shows up
+// twice.
+pub fn search_with_context<'a>(
+    matcher: &dyn Matcher,
+    contents: &'a str,
+    before: usize,
+    after: usize,
+) -> Vec<Vec<(usize, &'a str)>> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut groups: Vec<Vec<(usize, &str)>> = Vec::new();
+    // Index just past the last line already in a group
+    let mut end = 0;
+
+    for (i, line) in lines.iter().enumerate() {
+        if !matcher.matches(line) {
+            continue;
+        }
+        let start = i.saturating_sub(before);
+        let stop = (i + after + 1).min(lines.len());
+        let numbered = |range: std::ops::Range<usize>| range.map(|j| (j + 1, lines[j]));
+
+        match groups.last_mut() {
+            Some(group) if start <= end => group.extend(numbered(end..stop)),
+            _ => groups.push(numbered(start..stop).collect()),
+        }
+        end = stop;
+    }
+
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,7 +487,10 @@ safe, fast, productive.
 Pick three.
 Duct tape.";
 
-        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+        assert_eq!(
+            vec![(2, "safe, fast, productive.")],
+            search(query, contents)
+        );
     }
 
     #[test]
@@ -102,8 +503,349 @@ Pick three.
 Trust me.";
 
         assert_eq!(
-            vec!["Rust:", "Trust me."],
+            vec![(1, "Rust:"), (4, "Trust me.")],
             search_case_insensitive(query, contents)
         );
     }
+
+    #[test]
+    fn case_insensitive_unicode() {
+        let query = "strasse";
+        let contents = "\
+Hauptstrasse 1
+HAUPTSTRASSE 2
+Hauptstraße 3
+ÉCOLE";
+
+        // The ß line is left out: lowercasing doesn't turn "ß" into "ss"
+        assert_eq!(
+            vec![(1, "Hauptstrasse 1"), (2, "HAUPTSTRASSE 2")],
+            search_case_insensitive_unicode(query, contents)
+        );
+        assert_eq!(
+            vec![(4, "ÉCOLE")],
+            search_case_insensitive_unicode("école", contents)
+        );
+    }
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        let mut all = vec![String::from("minigrep")];
+        all.extend(args.iter().map(|arg| String::from(*arg)));
+        all.into_iter()
+    }
+
+    #[test]
+    fn separator_between_filename_and_line() {
+        let config = Config::build(args(&["-H", "--separator", "\t", "to", "poem.txt"])).unwrap();
+
+        assert_eq!(config.query, "to");
+        assert_eq!(config.file_paths, vec!["poem.txt"]);
+        assert_eq!(
+            config.format_line("poem.txt", 7, "Are you nobody, too?"),
+            "poem.txt\t7\tAre you nobody, too?"
+        );
+    }
+
+    #[test]
+    fn separator_after_line_number_without_filename() {
+        let config = Config::build(args(&["to", "poem.txt", "--separator", "\t"])).unwrap();
+
+        assert_eq!(
+            config.format_line("poem.txt", 7, "Are you nobody, too?"),
+            "7\tAre you nobody, too?"
+        );
+    }
+
+    #[test]
+    fn run_uses_separator_for_numbered_lines() {
+        let path = std::env::temp_dir().join("minigrep_run_uses_separator.txt");
+        fs::write(&path, "Rust:\nsafe, fast, productive.\nTrust me.").unwrap();
+        let path = path.to_str().unwrap();
+
+        let config = Config::build(args(&["--separator", " | ", "ust", path])).unwrap();
+        let mut output: Vec<String> = Vec::new();
+        run(config, &mut output).unwrap();
+
+        let config = Config::build(args(&["-H", "--separator", " | ", "Trust", path])).unwrap();
+        let mut with_filename: Vec<String> = Vec::new();
+        run(config, &mut with_filename).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(output, vec!["1 | Rust:", "3 | Trust me."]);
+        assert_eq!(with_filename, vec![format!("{path} | 3 | Trust me.")]);
+    }
+
+    #[test]
+    fn default_separator_is_a_colon() {
+        let config = Config::build(args(&["--with-filename", "to", "poem.txt"])).unwrap();
+
+        assert_eq!(
+            config.format_line("poem.txt", 7, "Are you nobody, too?"),
+            "poem.txt:7:Are you nobody, too?"
+        );
+    }
+
+    #[test]
+    fn dry_run_displays_parsed_config() {
+        let config = Config::build(args(&[
+            "--dry-run",
+            "-H",
+            "to",
+            "poem.txt",
+            "--separator",
+            "\t",
+        ]))
+        .unwrap();
+        let output = config.to_string();
+
+        assert!(config.dry_run);
+        assert!(output.contains("query=\"to\"\n"));
+        assert!(output.contains("file_paths=[\"poem.txt\"]\n"));
+        assert!(output.contains("with_filename=true\n"));
+        assert!(output.contains("separator=\"\\t\"\n"));
+        assert!(output.contains("dry_run=true\n"));
+        assert!(output.contains("before=0\n"));
+        assert!(output.contains("keep_crlf=false\n"));
+        assert!(output.ends_with("stats=false"));
+    }
+
+    #[test]
+    fn dry_run_skips_reading_files() {
+        let path = std::env::temp_dir().join("minigrep_dry_run_missing.txt");
+        let path = path.to_str().unwrap();
+
+        let config = Config::build(args(&["--dry-run", "to", path])).unwrap();
+        let expected = config.to_string();
+        let mut output: Vec<String> = Vec::new();
+
+        // The file doesn't exist, so run would fail if it tried to read it
+        assert!(run(config, &mut output).is_ok());
+        assert_eq!(output, vec![expected]);
+        assert!(output[0].contains(&format!("file_paths=[{:?}]\n", path)));
+    }
+
+    #[test]
+    fn stats_footer_counts_matches_across_files() {
+        let dir = std::env::temp_dir();
+        let first = dir.join("minigrep_stats_footer_first.txt");
+        let second = dir.join("minigrep_stats_footer_second.txt");
+        fs::write(&first, "Rust:\nsafe, fast, productive.\nPick three.").unwrap();
+        fs::write(&second, "Trust me.\nDuct tape.\nRusty nails.").unwrap();
+
+        let config = Config::build(args(&[
+            "--stats",
+            "Rust",
+            first.to_str().unwrap(),
+            second.to_str().unwrap(),
+        ]))
+        .unwrap();
+        let mut output: Vec<String> = Vec::new();
+        run(config, &mut output).unwrap();
+        fs::remove_file(&first).unwrap();
+        fs::remove_file(&second).unwrap();
+
+        assert_eq!(output.len(), 3);
+        assert_eq!(output[2], "2 matches in 2 files");
+    }
+
+    #[test]
+    fn stats_footer_for_a_single_file() {
+        let path = std::env::temp_dir().join("minigrep_stats_footer_single.txt");
+        fs::write(&path, "safe, fast, productive.\nPick three.").unwrap();
+
+        let config = Config::build(args(&["--stats", "duct", path.to_str().unwrap()])).unwrap();
+        let mut output: Vec<String> = Vec::new();
+        run(config, &mut output).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            output,
+            vec!["1:safe, fast, productive.", "1 match in 1 file"]
+        );
+    }
+
+    struct EvenLengthMatcher;
+
+    impl Matcher for EvenLengthMatcher {
+        fn matches(&self, line: &str) -> bool {
+            line.len().is_multiple_of(2)
+        }
+    }
+
+    #[test]
+    fn custom_matcher() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(
+            vec![(4, "Duct tape.")],
+            search_with(&EvenLengthMatcher, contents)
+        );
+    }
+
+    #[test]
+    fn config_picks_matcher_from_flags() {
+        let contents = "\
+Rust:
+Trust me.";
+        let mut config = Config::build(args(&["rUsT", "poem.txt"])).unwrap();
+
+        config.ignore_case = false;
+        assert_eq!(
+            search_with(config.matcher().as_ref(), contents),
+            Vec::<(usize, &str)>::new()
+        );
+
+        config.ignore_case = true;
+        assert_eq!(
+            search_with(config.matcher().as_ref(), contents),
+            vec![(1, "Rust:"), (2, "Trust me.")]
+        );
+    }
+
+    #[test]
+    fn run_into_recording_output() {
+        let path = std::env::temp_dir().join("minigrep_run_into_recording_output.txt");
+        fs::write(
+            &path,
+            "Rust:\nsafe, fast, productive.\nPick three.\nTrust me.",
+        )
+        .unwrap();
+
+        let config = Config::build(args(&["-H", "rust", path.to_str().unwrap()])).unwrap();
+        let mut output: Vec<String> = Vec::new();
+        run(config, &mut output).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(output, vec![format!("{}:4:Trust me.", path.display())]);
+    }
+
+    #[test]
+    fn detects_line_endings() {
+        assert_eq!(detect_line_ending("Rust:\r\nTrust me.\r\n"), "\r\n");
+        assert_eq!(detect_line_ending("Rust:\nTrust me.\n"), "\n");
+        assert_eq!(detect_line_ending("Rust: no newline"), "\n");
+    }
+
+    #[test]
+    fn keep_crlf_restores_carriage_returns() {
+        let path = std::env::temp_dir().join("minigrep_keep_crlf_restores_carriage_returns.txt");
+        fs::write(&path, "Rust:\r\nPick three.\r\nTrust me.\r\n").unwrap();
+
+        let config = Config::build(args(&["--keep-crlf", "rust", path.to_str().unwrap()])).unwrap();
+        let mut output: Vec<String> = Vec::new();
+        run(config, &mut output).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(output, vec!["3:Trust me.\r"]);
+    }
+
+    #[test]
+    fn run_parallel_sorts_hits_across_files() {
+        let dir = std::env::temp_dir();
+        let files = [
+            ("minigrep_parallel_a.txt", "Rust:\nsafe, fast, productive."),
+            ("minigrep_parallel_b.txt", "Pick three.\nDuct tape."),
+            ("minigrep_parallel_c.txt", "Trust me.\nrust\nRusty nails."),
+        ];
+        let mut paths = Vec::new();
+        for (name, contents) in files {
+            let path = dir.join(name);
+            fs::write(&path, contents).unwrap();
+            paths.push(String::from(path.to_str().unwrap()));
+        }
+
+        let mut all_args = vec!["ust"];
+        all_args.extend(paths.iter().map(|path| path.as_str()));
+        let config = Config::build(args(&all_args)).unwrap();
+        let pool = ThreadPool::new(3);
+        let hits = run_parallel(&config, &pool);
+        for path in &paths {
+            fs::remove_file(path).unwrap();
+        }
+
+        let hit = |file_index, line_number, line: &str| SearchHit {
+            file_index,
+            line_number,
+            line: String::from(line),
+        };
+        assert_eq!(
+            hits,
+            vec![
+                hit(0, 1, "Rust:"),
+                hit(2, 1, "Trust me."),
+                hit(2, 2, "rust"),
+                hit(2, 3, "Rusty nails."),
+            ]
+        );
+    }
+
+    #[test]
+    fn search_reports_line_numbers() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape, productively.";
+
+        assert_eq!(
+            search("product", contents),
+            vec![
+                (2, "safe, fast, productive."),
+                (4, "Duct tape, productively.")
+            ]
+        );
+    }
+
+    #[test]
+    fn context_windows_merge_when_they_overlap() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape, productively.
+Trust me.";
+        let config = Config::build(args(&["-C", "1", "product", "poem.txt"])).unwrap();
+
+        assert_eq!((config.before, config.after), (1, 1));
+        assert_eq!(
+            search_with_context(config.matcher().as_ref(), contents, 1, 1),
+            vec![vec![
+                (1, "Rust:"),
+                (2, "safe, fast, productive."),
+                (3, "Pick three."),
+                (4, "Duct tape, productively."),
+                (5, "Trust me."),
+            ]]
+        );
+    }
+
+    #[test]
+    fn context_windows_stay_apart_when_far() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Rusty nails.
+Trust me.
+Done.";
+        let matcher = SubstringMatcher::new("ust");
+
+        assert_eq!(
+            search_with_context(&matcher, contents, 1, 1),
+            vec![
+                vec![(1, "Rust:"), (2, "safe, fast, productive.")],
+                vec![
+                    (4, "Duct tape."),
+                    (5, "Rusty nails."),
+                    (6, "Trust me."),
+                    (7, "Done.")
+                ],
+            ]
+        );
+    }
 }
@@ -8,7 +8,7 @@
 use std::env;
 use std::process;
 
-use minigrep::Config;
+use minigrep::{Config, StdoutOutput};
 
 fn main() {
     // Passing ownership of the iterator returned from env::args to Config::build.
@@ -19,7 +19,7 @@ fn main() {
 
     // --snip--
 
-    if let Err(e) = minigrep::run(config) {
+    if let Err(e) = minigrep::run(config, &mut StdoutOutput) {
         eprintln!("Application error: {e}");
         process::exit(1);
     }
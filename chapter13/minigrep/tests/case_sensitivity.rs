@@ -0,0 +1,39 @@
+use std::fs;
+
+use minigrep::Config;
+
+fn config_for(query: &str, file_path: &str) -> Config {
+    Config {
+        query: String::from(query),
+        file_paths: vec![String::from(file_path)],
+        ignore_case: false,
+        with_filename: false,
+        separator: String::from(":"),
+        dry_run: false,
+        stats: false,
+        keep_crlf: false,
+        before: 0,
+        after: 0,
+    }
+}
+
+#[test]
+fn ignore_case_flag_decides_the_matches() {
+    let path = std::env::temp_dir().join("minigrep_ignore_case_flag_decides_the_matches.txt");
+    fs::write(
+        &path,
+        "Rust:\nsafe, fast, productive.\nPick three.\nTrust me.",
+    )
+    .unwrap();
+    let config = config_for("rUsT", path.to_str().unwrap());
+
+    let mut output: Vec<String> = Vec::new();
+    minigrep::run(config.clone(), &mut output).unwrap();
+    assert!(output.is_empty());
+
+    let mut output: Vec<String> = Vec::new();
+    minigrep::run(config.with_case_insensitive(), &mut output).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output, vec!["1:Rust:", "4:Trust me."]);
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+use minigrep::Config;
+
+fn args(args: &[&str]) -> impl Iterator<Item = String> {
+    let mut all = vec![String::from("minigrep")];
+    all.extend(args.iter().map(|arg| String::from(*arg)));
+    all.into_iter()
+}
+
+#[test]
+fn matches_are_prefixed_with_their_file() {
+    let dir = std::env::temp_dir();
+    let first = dir.join("minigrep_matches_are_prefixed_first.txt");
+    let second = dir.join("minigrep_matches_are_prefixed_second.txt");
+    fs::write(&first, "Rust:\nsafe, fast, productive.\nPick three.").unwrap();
+    fs::write(&second, "Duct tape.\nTrust me.\nproductive people").unwrap();
+    let first = first.to_str().unwrap();
+    let second = second.to_str().unwrap();
+
+    let config = Config::build(args(&["productive", first, second])).unwrap();
+    let mut output: Vec<String> = Vec::new();
+    minigrep::run(config, &mut output).unwrap();
+
+    let config = Config::build(args(&["Trust", first, second])).unwrap();
+    let mut trust: Vec<String> = Vec::new();
+    minigrep::run(config, &mut trust).unwrap();
+
+    let config = Config::build(args(&["Trust", second])).unwrap();
+    let mut single: Vec<String> = Vec::new();
+    minigrep::run(config, &mut single).unwrap();
+
+    fs::remove_file(first).unwrap();
+    fs::remove_file(second).unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            format!("{first}:2:safe, fast, productive."),
+            format!("{second}:3:productive people"),
+        ]
+    );
+    assert_eq!(trust, vec![format!("{second}:2:Trust me.")]);
+    // A single source needs no prefix
+    assert_eq!(single, vec!["2:Trust me."]);
+}
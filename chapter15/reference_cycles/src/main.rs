@@ -47,6 +47,7 @@ and memory leaks.
 
 use crate::List::{Cons, Nil};
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::rc::{Rc, Weak};
 
 fn main() {
@@ -90,6 +91,14 @@ fn main() {
     *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
 
     println!("leaf parent = {:?}", leaf.parent.borrow().upgrade());
+    println!("tree:\n{}", pretty(&branch));
+    println!("leaves = {:?}", leaves(&branch));
+    println!(
+        "sum = {}, average = {}",
+        sum_values(&branch),
+        average_value(&branch)
+    );
+    println!("levels = {:?}", bfs_levels(&branch));
 
     // Changes to strong_count to weak_count
     let leaf = Rc::new(Node {
@@ -160,3 +169,143 @@ struct Node {
     parent: RefCell<Weak<Node>>,
     children: RefCell<Vec<Rc<Node>>>,
 }
+
+// Indented text form of the tree, one value per line: a node, then each of its children
+// (depth first), two more spaces of indentation per level.
+fn pretty(root: &Rc<Node>) -> String {
+    let mut lines = Vec::new();
+    pretty_lines(root, 0, &mut lines);
+    lines.join("\n")
+}
+
+fn pretty_lines(node: &Rc<Node>, depth: usize, lines: &mut Vec<String>) {
+    lines.push(format!("{}{}", "  ".repeat(depth), node.value));
+    for child in node.children.borrow().iter() {
+        pretty_lines(child, depth + 1, lines);
+    }
+}
+
+// Values of the nodes without children, depth first
+fn leaves(root: &Rc<Node>) -> Vec<i32> {
+    let children = root.children.borrow();
+    if children.is_empty() {
+        return vec![root.value];
+    }
+    children.iter().flat_map(leaves).collect()
+}
+
+// Sum of every value in the tree. The values are i32, so the total is kept as an i64 to
+// leave room for large trees.
+fn sum_values(root: &Rc<Node>) -> i64 {
+    let children: i64 = root.children.borrow().iter().map(sum_values).sum();
+    i64::from(root.value) + children
+}
+
+// A tree always has at least its root, so this never divides by zero
+fn average_value(root: &Rc<Node>) -> f64 {
+    sum_values(root) as f64 / count_nodes(root) as f64
+}
+
+fn count_nodes(root: &Rc<Node>) -> usize {
+    1 + root
+        .children
+        .borrow()
+        .iter()
+        .map(count_nodes)
+        .sum::<usize>()
+}
+
+// Breadth-first walk: values grouped by depth, the root alone at level 0. The queue holds
+// clones of the Rc pointers (not the nodes), each with the depth it was found at.
+fn bfs_levels(root: &Rc<Node>) -> Vec<Vec<i32>> {
+    let mut levels: Vec<Vec<i32>> = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back((Rc::clone(root), 0));
+
+    while let Some((node, depth)) = queue.pop_front() {
+        if levels.len() == depth {
+            levels.push(Vec::new());
+        }
+        levels[depth].push(node.value);
+
+        for child in node.children.borrow().iter() {
+            queue.push_back((Rc::clone(child), depth + 1));
+        }
+    }
+
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds a node that owns children and sets itself as their parent
+    fn node(value: i32, children: Vec<Rc<Node>>) -> Rc<Node> {
+        let node = Rc::new(Node {
+            value,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(children),
+        });
+        for child in node.children.borrow().iter() {
+            *child.parent.borrow_mut() = Rc::downgrade(&node);
+        }
+        node
+    }
+
+    #[test]
+    fn pretty_prints_indented_tree() {
+        let tree = node(
+            1,
+            vec![
+                node(2, vec![node(4, vec![]), node(5, vec![])]),
+                node(3, vec![]),
+            ],
+        );
+
+        assert_eq!(pretty(&tree), "1\n  2\n    4\n    5\n  3");
+        assert_eq!(pretty(&node(7, vec![])), "7");
+    }
+
+    #[test]
+    fn leaves_in_depth_first_order() {
+        let tree = node(
+            1,
+            vec![
+                node(2, vec![node(4, vec![]), node(5, vec![node(8, vec![])])]),
+                node(3, vec![]),
+                node(6, vec![node(7, vec![])]),
+            ],
+        );
+
+        assert_eq!(leaves(&tree), vec![4, 8, 3, 7]);
+        assert_eq!(leaves(&node(9, vec![])), vec![9]);
+    }
+
+    #[test]
+    fn sum_and_average_of_values() {
+        let tree = node(
+            1,
+            vec![
+                node(2, vec![node(4, vec![]), node(5, vec![])]),
+                node(3, vec![]),
+            ],
+        );
+
+        assert_eq!(sum_values(&tree), 15);
+        assert_eq!(average_value(&tree), 3.0);
+    }
+
+    #[test]
+    fn bfs_groups_values_by_level() {
+        let tree = node(
+            1,
+            vec![
+                node(2, vec![node(4, vec![]), node(5, vec![])]),
+                node(3, vec![node(6, vec![])]),
+            ],
+        );
+
+        assert_eq!(bfs_levels(&tree), vec![vec![1], vec![2, 3], vec![4, 5, 6]]);
+    }
+}
@@ -63,6 +63,7 @@ fn main() {
     // List value will take up the size of an i32 plus the size of a box’s pointer data.
     let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
     println!("List: {:#?}", list);
+    println!("Reversed: {:?}, last: {:?}", list.reversed(), list.last());
 
     // Dereference a variable to assert
     let x = 5;
@@ -101,6 +102,35 @@ enum List {
     Nil,
 }
 
+impl List {
+    // Walks the list with a loop instead of recursion, borrowing each node in turn, so
+    // the list is not consumed and long lists don't grow the stack.
+    fn reversed(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            values.push(*value);
+            current = next;
+        }
+
+        values.reverse();
+        values
+    }
+
+    fn last(&self) -> Option<i32> {
+        let mut last = None;
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            last = Some(*value);
+            current = next;
+        }
+
+        last
+    }
+}
+
 // Define a custom Box type
 // The MyBox type is a tuple struct with one element of type T.
 struct MyBox<T>(T);
@@ -128,3 +158,22 @@ impl<T> Deref for MyBox<T> {
 fn hello(name: &str) {
     println!("Hello, {} !", name);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reversed_and_last_of_three_elements() {
+        let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+        assert_eq!(list.reversed(), vec![3, 2, 1]);
+        assert_eq!(list.last(), Some(3));
+    }
+
+    #[test]
+    fn reversed_and_last_of_nil() {
+        assert_eq!(Nil.reversed(), Vec::<i32>::new());
+        assert_eq!(Nil.last(), None);
+    }
+}
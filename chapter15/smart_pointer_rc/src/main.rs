@@ -43,9 +43,45 @@ fn main() {
         println!("count after creating c = {}", Rc::strong_count(&a));
     }
     println!("count after c goes out of scope = {}", Rc::strong_count(&a));
+
+    // b shares the tail a, so values of a can be found walking from b as well.
+    println!("b contains 10 = {}", contains(&b, 10));
 }
 
 enum List {
     Cons(i32, Rc<List>),
     Nil,
 }
+
+// Walks the chain of Rc<List> without taking ownership of any node. Lists that share a
+// tail share its values too, so a value in the shared part is found from any of them.
+fn contains(list: &List, value: i32) -> bool {
+    let mut current = list;
+
+    while let Cons(item, next) = current {
+        if *item == value {
+            return true;
+        }
+        current = next;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_tail_values_are_found_from_every_list() {
+        let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
+        let b = Cons(3, Rc::clone(&a));
+        let c = Cons(4, Rc::clone(&a));
+
+        assert!(contains(&b, 10));
+        assert!(contains(&c, 10));
+        assert!(contains(&b, 3));
+        assert!(!contains(&c, 3));
+        assert!(!contains(&a, 4));
+    }
+}
@@ -25,10 +25,143 @@ pub trait Messenger {
     fn send(&self, msg: &str);
 }
 
-pub struct LimitTracker<'a, T: Messenger> {
-    messenger: &'a T,
+// How close the value is to the maximum, from below 75% up to over the quota
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuotaLevel {
+    Ok,
+    Warn,
+    Urgent,
+    Over,
+}
+
+// Fractions of max where the warning and the urgent warning start. Being over the quota
+// always starts at 1.0.
+#[derive(Clone, Copy)]
+struct Thresholds {
+    warn: f64,
+    urgent: f64,
+}
+
+impl Thresholds {
+    const DEFAULT: Thresholds = Thresholds {
+        warn: 0.75,
+        urgent: 0.9,
+    };
+
+    fn level(&self, percentage_of_max: f64) -> QuotaLevel {
+        if percentage_of_max >= 1.0 {
+            QuotaLevel::Over
+        } else if percentage_of_max >= self.urgent {
+            QuotaLevel::Urgent
+        } else if percentage_of_max >= self.warn {
+            QuotaLevel::Warn
+        } else {
+            QuotaLevel::Ok
+        }
+    }
+
+    // Level of value against max. With max at 0 any usage at all is over the quota, and
+    // no usage is Ok.
+    fn level_of(&self, value: usize, max: usize) -> QuotaLevel {
+        if max == 0 {
+            return if value > 0 {
+                QuotaLevel::Over
+            } else {
+                QuotaLevel::Ok
+            };
+        }
+        self.level(value as f64 / max as f64)
+    }
+
+    // What to tell the user when entering a level; Ok has nothing to say
+    fn message(&self, level: QuotaLevel) -> Option<String> {
+        // Rounded so 0.9 shows as 90 and not 90.00000000000001
+        let percent = |threshold: f64| (threshold * 100.0).round();
+        match level {
+            QuotaLevel::Over => Some(String::from("Error: You are over your quota!")),
+            QuotaLevel::Urgent => Some(format!(
+                "Urgent warning: You've used up over {}% of your quota!",
+                percent(self.urgent)
+            )),
+            QuotaLevel::Warn => Some(format!(
+                "Warning: You've used up over {}% of your quota!",
+                percent(self.warn)
+            )),
+            QuotaLevel::Ok => None,
+        }
+    }
+}
+
+// The tracking both LimitTracker and MultiLimitTracker do: storing the value, working out
+// its level and keeping the history. record says which message to send, if any, and the
+// trackers only decide who to send it to.
+struct Usage {
     value: usize,
     max: usize,
+    thresholds: Thresholds,
+    // Level of the last recorded value, so a message is only sent when the level changes
+    last_level: QuotaLevel,
+    // Every recorded value, with the level it resulted in
+    history: Vec<(usize, QuotaLevel)>,
+}
+
+impl Usage {
+    // Both trackers build their Usage here, so they validate thresholds the same way
+    fn new(max: usize, warn: f64, urgent: f64) -> Result<Usage, &'static str> {
+        if !(warn < urgent && urgent <= 1.0) {
+            return Err("thresholds must satisfy warn < urgent <= 1.0");
+        }
+
+        Ok(Usage {
+            value: 0,
+            max,
+            thresholds: Thresholds { warn, urgent },
+            last_level: QuotaLevel::Ok,
+            history: vec![],
+        })
+    }
+
+    fn set_value(&mut self, value: usize) -> Option<String> {
+        let level = self.thresholds.level_of(value, self.max);
+        self.record(value, level)
+    }
+
+    // The level comes from the raw input, so going past max sends the error message just
+    // as set_value would, even when max is 0 and the stored value is 0.
+    fn set_value_clamped(&mut self, value: usize) -> Option<String> {
+        let level = self.thresholds.level_of(value, self.max);
+        self.record(value.min(self.max), level)
+    }
+
+    fn record(&mut self, value: usize, level: QuotaLevel) -> Option<String> {
+        self.value = value;
+        self.history.push((value, level));
+
+        // Staying at the same level (say, hovering above 90%) would repeat the same warning
+        if level == self.last_level {
+            return None;
+        }
+        self.last_level = level;
+
+        self.thresholds.message(level)
+    }
+
+    // With max at 0 there's nothing to divide by, so the percentage is 0.0 instead of NaN.
+    fn percentage(&self) -> f64 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        self.value as f64 / self.max as f64
+    }
+
+    fn remaining(&self) -> usize {
+        self.max.saturating_sub(self.value)
+    }
+}
+
+pub struct LimitTracker<'a, T: Messenger> {
+    messenger: &'a T,
+    usage: Usage,
 }
 
 impl<'a, T> LimitTracker<'a, T>
@@ -36,34 +169,141 @@ where
     T: Messenger,
 {
     pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
-        return LimitTracker {
+        let Thresholds { warn, urgent } = Thresholds::DEFAULT;
+        return LimitTracker::with_thresholds(messenger, max, warn, urgent)
+            .expect("default thresholds should be valid");
+    }
+
+    // Custom thresholds, as fractions of max. Being over the quota always starts at 1.0,
+    // so the warnings have to come before it: warn < urgent <= 1.0.
+    pub fn with_thresholds(
+        messenger: &'a T,
+        max: usize,
+        warn: f64,
+        urgent: f64,
+    ) -> Result<LimitTracker<'a, T>, &'static str> {
+        Ok(LimitTracker {
             messenger,
-            value: 0,
-            max,
-        };
+            usage: Usage::new(max, warn, urgent)?,
+        })
     }
 
     pub fn set_value(&mut self, value: usize) {
-        self.value = value;
+        if let Some(message) = self.usage.set_value(value) {
+            self.messenger.send(&message);
+        }
+    }
 
-        let percentage_of_max = self.value as f64 / self.max as f64;
+    // Like set_value, but never stores more than max, so the tracked usage tops out at 100%.
+    // Going past max still sends the error message.
+    pub fn set_value_clamped(&mut self, value: usize) {
+        if let Some(message) = self.usage.set_value_clamped(value) {
+            self.messenger.send(&message);
+        }
+    }
 
-        if percentage_of_max >= 1.0 {
-            self.messenger.send("Error: You are over your quota!");
-        } else if percentage_of_max >= 0.9 {
-            self.messenger
-                .send("Urgent warning: You've used up over 90% of your quota!");
-        } else if percentage_of_max >= 0.75 {
-            self.messenger
-                .send("Warning: You've used up over 75% of your quota!");
+    // Read-only views of the usage, for things like a progress bar. They never send messages.
+    pub fn percentage(&self) -> f64 {
+        self.usage.percentage()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.usage.remaining()
+    }
+
+    pub fn history(&self) -> &[(usize, QuotaLevel)] {
+        &self.usage.history
+    }
+}
+
+// Same tracking as LimitTracker, but every message goes to several messengers (say, email
+// and chat). The messengers are trait objects, so they don't all have to be the same type.
+pub struct MultiLimitTracker<'a> {
+    messengers: Vec<&'a dyn Messenger>,
+    usage: Usage,
+}
+
+impl<'a> MultiLimitTracker<'a> {
+    pub fn new(messengers: Vec<&'a dyn Messenger>, max: usize) -> MultiLimitTracker<'a> {
+        let Thresholds { warn, urgent } = Thresholds::DEFAULT;
+        MultiLimitTracker::with_thresholds(messengers, max, warn, urgent)
+            .expect("default thresholds should be valid")
+    }
+
+    // Same rules as LimitTracker::with_thresholds
+    pub fn with_thresholds(
+        messengers: Vec<&'a dyn Messenger>,
+        max: usize,
+        warn: f64,
+        urgent: f64,
+    ) -> Result<MultiLimitTracker<'a>, &'static str> {
+        Ok(MultiLimitTracker {
+            messengers,
+            usage: Usage::new(max, warn, urgent)?,
+        })
+    }
+
+    pub fn set_value(&mut self, value: usize) {
+        let message = self.usage.set_value(value);
+        self.send_all(message);
+    }
+
+    pub fn set_value_clamped(&mut self, value: usize) {
+        let message = self.usage.set_value_clamped(value);
+        self.send_all(message);
+    }
+
+    fn send_all(&self, message: Option<String>) {
+        if let Some(message) = message {
+            for messenger in &self.messengers {
+                messenger.send(&message);
+            }
+        }
+    }
+
+    pub fn percentage(&self) -> f64 {
+        self.usage.percentage()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.usage.remaining()
+    }
+
+    pub fn history(&self) -> &[(usize, QuotaLevel)] {
+        &self.usage.history
+    }
+}
+
+// Decorator over Messenger
+// PrefixMessenger is a Messenger itself, so it can be handed to a LimitTracker in place of
+// the messenger it wraps. Every message gets the prefix and is forwarded to the inner one.
+pub struct PrefixMessenger<'a, M: Messenger> {
+    inner: &'a M,
+    prefix: String,
+}
+
+impl<'a, M> PrefixMessenger<'a, M>
+where
+    M: Messenger,
+{
+    pub fn new(inner: &'a M, prefix: &str) -> PrefixMessenger<'a, M> {
+        PrefixMessenger {
+            inner,
+            prefix: String::from(prefix),
         }
     }
 }
 
+impl<M: Messenger> Messenger for PrefixMessenger<'_, M> {
+    fn send(&self, msg: &str) {
+        self.inner.send(&format!("{}{}", self.prefix, msg));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     struct MockMessenger {
         sent_messages: RefCell<Vec<String>>,
@@ -92,4 +332,220 @@ mod tests {
 
         assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
     }
+
+    #[test]
+    fn prefix_messenger_forwards_with_prefix() {
+        let mock_messenger = MockMessenger::new();
+        let prefixed = PrefixMessenger::new(&mock_messenger, "[api] ");
+        let mut limit_tracker = LimitTracker::new(&prefixed, 100);
+
+        limit_tracker.set_value(80);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![String::from(
+                "[api] Warning: You've used up over 75% of your quota!"
+            )]
+        );
+    }
+
+    #[test]
+    fn history_records_each_value_and_level() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(10);
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(95);
+        limit_tracker.set_value(120);
+        limit_tracker.set_value(50);
+
+        assert_eq!(
+            limit_tracker.history(),
+            &[
+                (10, QuotaLevel::Ok),
+                (80, QuotaLevel::Warn),
+                (95, QuotaLevel::Urgent),
+                (120, QuotaLevel::Over),
+                (50, QuotaLevel::Ok),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_value_clamped_stores_max_and_still_warns() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value_clamped(150);
+
+        assert_eq!(limit_tracker.usage.value, 100);
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![String::from("Error: You are over your quota!")]
+        );
+    }
+
+    #[test]
+    fn custom_thresholds() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker =
+            LimitTracker::with_thresholds(&mock_messenger, 100, 0.5, 0.8).unwrap();
+
+        limit_tracker.set_value(40);
+        limit_tracker.set_value(60);
+        limit_tracker.set_value(85);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![
+                String::from("Warning: You've used up over 50% of your quota!"),
+                String::from("Urgent warning: You've used up over 80% of your quota!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn thresholds_out_of_order_are_rejected() {
+        let mock_messenger = MockMessenger::new();
+
+        assert!(LimitTracker::with_thresholds(&mock_messenger, 100, 0.8, 0.5).is_err());
+        assert!(LimitTracker::with_thresholds(&mock_messenger, 100, 0.5, 0.5).is_err());
+        assert!(LimitTracker::with_thresholds(&mock_messenger, 100, 0.5, 1.2).is_err());
+    }
+
+    #[test]
+    fn same_level_is_sent_once() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(92);
+        limit_tracker.set_value(93);
+
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+    }
+
+    #[test]
+    fn level_change_sends_again() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(92);
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(10);
+        limit_tracker.set_value(91);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![
+                String::from("Urgent warning: You've used up over 90% of your quota!"),
+                String::from("Warning: You've used up over 75% of your quota!"),
+                String::from("Urgent warning: You've used up over 90% of your quota!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn percentage_and_remaining() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 120);
+
+        limit_tracker.set_value(30);
+
+        assert_eq!(limit_tracker.percentage(), 0.25);
+        assert_eq!(limit_tracker.remaining(), 90);
+        assert!(mock_messenger.sent_messages.borrow().is_empty());
+    }
+
+    #[test]
+    fn percentage_with_zero_max() {
+        let mock_messenger = MockMessenger::new();
+        let limit_tracker = LimitTracker::new(&mock_messenger, 0);
+
+        assert_eq!(limit_tracker.percentage(), 0.0);
+        assert_eq!(limit_tracker.remaining(), 0);
+    }
+
+    // A second kind of messenger, so the fan-out mixes types
+    struct CountingMessenger {
+        sent: Cell<usize>,
+    }
+
+    impl Messenger for CountingMessenger {
+        fn send(&self, _message: &str) {
+            self.sent.set(self.sent.get() + 1);
+        }
+    }
+
+    #[test]
+    fn multi_tracker_sends_to_every_messenger() {
+        let email = MockMessenger::new();
+        let chat = CountingMessenger { sent: Cell::new(0) };
+        let mut tracker = MultiLimitTracker::new(vec![&email, &chat], 100);
+
+        tracker.set_value(120);
+        tracker.set_value(130);
+
+        assert_eq!(
+            *email.sent_messages.borrow(),
+            vec![String::from("Error: You are over your quota!")]
+        );
+        assert_eq!(chat.sent.get(), 1);
+    }
+
+    #[test]
+    fn multi_tracker_with_custom_thresholds() {
+        let email = MockMessenger::new();
+        let chat = MockMessenger::new();
+        let mut tracker =
+            MultiLimitTracker::with_thresholds(vec![&email, &chat], 100, 0.5, 0.8).unwrap();
+
+        tracker.set_value(60);
+        tracker.set_value(70);
+        tracker.set_value_clamped(150);
+
+        let expected = vec![
+            String::from("Warning: You've used up over 50% of your quota!"),
+            String::from("Error: You are over your quota!"),
+        ];
+        assert_eq!(*email.sent_messages.borrow(), expected);
+        assert_eq!(*chat.sent_messages.borrow(), expected);
+        assert_eq!(
+            tracker.history(),
+            &[
+                (60, QuotaLevel::Warn),
+                (70, QuotaLevel::Warn),
+                (100, QuotaLevel::Over),
+            ]
+        );
+        assert_eq!(tracker.percentage(), 1.0);
+        assert_eq!(tracker.remaining(), 0);
+
+        assert!(MultiLimitTracker::with_thresholds(vec![&email], 100, 0.8, 0.5).is_err());
+    }
+
+    #[test]
+    fn zero_max_is_over_quota_for_any_usage() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 0);
+
+        limit_tracker.set_value(0);
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 0);
+
+        limit_tracker.set_value(5);
+        assert_eq!(
+            mock_messenger.sent_messages.borrow().as_slice(),
+            ["Error: You are over your quota!"]
+        );
+        assert_eq!(limit_tracker.history()[1], (5, QuotaLevel::Over));
+        assert_eq!(limit_tracker.percentage(), 0.0);
+
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 0);
+        limit_tracker.set_value_clamped(5);
+        assert_eq!(limit_tracker.history(), [(0, QuotaLevel::Over)]);
+
+        let mut multi = MultiLimitTracker::new(vec![&mock_messenger], 0);
+        multi.set_value(1);
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 3);
+    }
 }
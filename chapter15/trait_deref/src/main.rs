@@ -50,6 +50,7 @@ must be the only reference to that data (otherwise, the program wouldn’t compi
 
 */
 
+use std::borrow::Borrow;
 use std::ops::Deref;
 
 use crate::List::{Cons, Nil};
@@ -125,6 +126,35 @@ impl<T> Deref for MyBox<T> {
     }
 }
 
+// Deref coercion only happens where the compiler knows the target type. Generic APIs
+// written as T: AsRef<U> or T: Borrow<U> need the trait itself, so MyBox implements both
+// by handing out a reference to the value inside.
+impl<T> AsRef<T> for MyBox<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Borrow<T> for MyBox<T> {
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
 fn hello(name: &str) {
     println!("Hello, {} !", name);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn my_box_as_ref_and_borrow() {
+        let takes_string = |s: &String| s.len();
+        let m = MyBox::new(String::from("hi"));
+
+        assert_eq!(takes_string(m.as_ref()), 2);
+        assert_eq!(takes_string(m.borrow()), 2);
+    }
+}
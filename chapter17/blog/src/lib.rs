@@ -8,7 +8,7 @@ pattern.
 The final functionality will look like this:
 - A blog post starts as an empty draft.
 - When the draft is done, a review of the post is requested.
-- When the post is approved, it gets published.
+- When the post is approved twice, it gets published.
 - Only published blog posts return content to print, so unapproved posts can’t
 accidentally be published.
 
@@ -27,6 +27,8 @@ solution.
 pub struct Post {
     state: Option<Box<dyn State>>,
     content: String,
+    // Names of the states the post has been in, oldest first
+    history: Vec<String>,
 }
 
 impl Post {
@@ -34,6 +36,7 @@ impl Post {
         Post {
             state: Some(Box::new(Draft {})),
             content: String::new(),
+            history: vec![String::from("Draft")],
         }
     }
 
@@ -49,22 +52,60 @@ impl Post {
         self.state.as_ref().unwrap().content(self)
     }
 
+    // Name of the current state, handy when debugging a workflow
+    pub fn state_name(&self) -> &'static str {
+        self.state.as_ref().unwrap().name()
+    }
+
+    // Whether the post is waiting for an approval, so a UI can disable its "approve"
+    // button otherwise
+    pub fn can_publish(&self) -> bool {
+        self.state.as_ref().unwrap().can_approve()
+    }
+
     // Requesting a review of the post changes its state
     pub fn request_review(&mut self) {
-        if let Some(s) = self.state.take() {
-            self.state = Some(s.request_review())
-        }
+        self.transition(|s| s.request_review());
     }
 
     // Set state to the value that the current state says it should have when that
     // state is approved
     pub fn approve(&mut self) {
+        self.transition(|s| s.approve());
+    }
+
+    // Back to a draft from any state. The text stays, but it is hidden again until the
+    // post goes through review and approval once more.
+    pub fn reset(&mut self) {
+        self.transition(|_| Box::new(Draft {}));
+    }
+
+    pub fn history(&self) -> Vec<String> {
+        self.history.clone()
+    }
+
+    // Every state change goes through here so it ends up in the history. Calls that leave
+    // the post in the same state (like approving a draft) are not recorded.
+    fn transition(&mut self, change: impl FnOnce(Box<dyn State>) -> Box<dyn State>) {
         if let Some(s) = self.state.take() {
-            self.state = Some(s.approve())
+            let before = s.name();
+            let after = change(s);
+            if after.name() != before {
+                self.history.push(String::from(after.name()));
+            }
+            self.state = Some(after);
         }
     }
 }
 
+// Approving a whole batch at once. Only posts waiting for an approval change: approve on a
+// draft or on a published post leaves it in the same state, so a mixed batch is safe.
+pub fn approve_all(posts: &mut [Post]) {
+    for post in posts.iter_mut() {
+        post.approve();
+    }
+}
+
 // Default implementations of the methods:
 trait State {
     /*
@@ -82,6 +123,12 @@ trait State {
     fn content<'a>(&self, post: &'a Post) -> &'a str {
         return "";
     }
+    // Every state reports its own name, there is no sensible default
+    fn name(&self) -> &'static str;
+    // Only a post waiting for review or for its second approval can be approved
+    fn can_approve(&self) -> bool {
+        false
+    }
 }
 
 struct Draft {}
@@ -94,6 +141,10 @@ impl State for Draft {
     fn approve(self: Box<Self>) -> Box<dyn State> {
         return self;
     }
+
+    fn name(&self) -> &'static str {
+        "Draft"
+    }
 }
 
 struct PendingReview {}
@@ -105,8 +156,39 @@ impl State for PendingReview {
         return self;
     }
 
+    // The first approval isn't enough to publish
     fn approve(self: Box<Self>) -> Box<dyn State> {
-        return Box::new(Published {});
+        return Box::new(ApprovedOnce {});
+    }
+
+    fn name(&self) -> &'static str {
+        "PendingReview"
+    }
+
+    fn can_approve(&self) -> bool {
+        true
+    }
+}
+
+// Editorial sign-off takes two approvals. After the first one the post waits here, and
+// like every state before Published it keeps the default content, so it stays hidden.
+struct ApprovedOnce {}
+
+impl State for ApprovedOnce {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        Box::new(Published {})
+    }
+
+    fn name(&self) -> &'static str {
+        "ApprovedOnce"
+    }
+
+    fn can_approve(&self) -> bool {
+        true
     }
 }
 
@@ -124,4 +206,121 @@ impl State for Published {
     fn content<'a>(&self, post: &'a Post) -> &'a str {
         return &post.content;
     }
+
+    fn name(&self) -> &'static str {
+        "Published"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_name_follows_the_workflow() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        assert_eq!(post.state_name(), "Draft");
+
+        post.request_review();
+        assert_eq!(post.state_name(), "PendingReview");
+
+        post.approve();
+        assert_eq!(post.state_name(), "ApprovedOnce");
+
+        post.approve();
+        assert_eq!(post.state_name(), "Published");
+    }
+
+    #[test]
+    fn can_publish_only_while_waiting_for_approval() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        assert!(!post.can_publish());
+
+        post.request_review();
+        assert!(post.can_publish());
+
+        post.approve();
+        assert!(post.can_publish());
+
+        post.approve();
+        assert!(!post.can_publish());
+    }
+
+    #[test]
+    fn approve_all_publishes_only_pending_posts() {
+        let mut draft = Post::new();
+        draft.add_text("Still a draft");
+        let mut pending = Post::new();
+        pending.add_text("Ready for review");
+        pending.request_review();
+
+        let mut posts = vec![draft, pending];
+        approve_all(&mut posts);
+        approve_all(&mut posts);
+
+        assert_eq!(posts[0].state_name(), "Draft");
+        assert_eq!(posts[0].content(), "");
+        assert_eq!(posts[1].state_name(), "Published");
+        assert_eq!(posts[1].content(), "Ready for review");
+    }
+
+    #[test]
+    fn history_records_each_transition() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+
+        post.approve();
+        post.request_review();
+        post.approve();
+        post.approve();
+
+        assert_eq!(
+            post.history(),
+            vec!["Draft", "PendingReview", "ApprovedOnce", "Published"]
+        );
+    }
+
+    #[test]
+    fn reset_returns_to_draft_and_keeps_text() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        post.request_review();
+        post.approve();
+        post.approve();
+
+        post.reset();
+
+        assert_eq!(post.state_name(), "Draft");
+        assert_eq!(post.content(), "");
+        assert_eq!(
+            post.history(),
+            vec![
+                "Draft",
+                "PendingReview",
+                "ApprovedOnce",
+                "Published",
+                "Draft"
+            ]
+        );
+
+        post.request_review();
+        post.approve();
+        post.approve();
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+    }
+
+    #[test]
+    fn publishing_needs_two_approvals() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        post.request_review();
+
+        post.approve();
+        assert_eq!(post.content(), "");
+
+        post.approve();
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+    }
 }
@@ -24,6 +24,10 @@ fn main() {
     post.request_review();
     assert_eq!("", post.content());
 
+    // One approval is not enough to publish
+    post.approve();
+    assert_eq!("", post.content());
+
     post.approve();
     assert_eq!("I ate a salad for lunch today", post.content());
 }
@@ -18,6 +18,8 @@ we’ve now encoded the blog post workflow into the type system.
 
 */
 
+use std::marker::PhantomData;
+
 pub struct Post {
     content: String,
 }
@@ -43,6 +45,25 @@ impl DraftPost {
         self.content.push_str(text);
     }
 
+    // Like add_text, but refuses text that would take the post over max_words. Words are
+    // counted on the content as it would be after the append, so text continuing a word
+    // ("salad" + "s") doesn't count as a new one. On error the content is left as it was.
+    pub fn add_text_limited(&mut self, text: &str, max_words: usize) -> Result<(), String> {
+        let words = format!("{}{}", self.content, text)
+            .split_whitespace()
+            .count();
+
+        if words > max_words {
+            return Err(format!(
+                "the post would have {} words, the limit is {}",
+                words, max_words
+            ));
+        }
+
+        self.add_text(text);
+        Ok(())
+    }
+
     // Takes ownership, consuming the DraftPost
     pub fn request_review(self) -> PendingReviewPost {
         return PendingReviewPost {
@@ -62,4 +83,350 @@ impl PendingReviewPost {
             content: self.content,
         };
     }
+
+    // A rejected post goes back to being a draft with the same content, so it can be
+    // edited and sent for review again. It never becomes a Post on the way, so the
+    // rejected text still can't be read as published.
+    pub fn reject(self) -> DraftPost {
+        DraftPost {
+            content: self.content,
+        }
+    }
+}
+
+// Each transition consumes the previous value, so once a post moved on there is no old
+// value left to go back to. PostHistory layers the history outside of the types: it runs
+// the transitions for us and keeps a copy of the content every time, so undo can hand
+// back a fresh DraftPost with the content the post had before its last change.
+//
+// undo and redo take the live post by value, so the post being undone is gone once the
+// restored draft exists. Redo gives back a draft too: a Post can only come from approve.
+#[derive(Default)]
+pub struct PostHistory {
+    snapshots: Vec<String>,
+    undone: Vec<String>,
+}
+
+// Only the post types of this crate can be handed to PostHistory::undo. The trait with
+// into_content lives in a private module, so code outside the crate can't call it to read
+// the content of a post that isn't published.
+mod sealed {
+    pub trait Sealed {
+        fn into_content(self) -> String;
+    }
+}
+
+pub trait PostState: sealed::Sealed {}
+
+impl sealed::Sealed for DraftPost {
+    fn into_content(self) -> String {
+        self.content
+    }
+}
+
+impl sealed::Sealed for PendingReviewPost {
+    fn into_content(self) -> String {
+        self.content
+    }
+}
+
+impl sealed::Sealed for Post {
+    fn into_content(self) -> String {
+        self.content
+    }
+}
+
+impl PostState for DraftPost {}
+
+impl PostState for PendingReviewPost {}
+
+impl PostState for Post {}
+
+impl PostHistory {
+    pub fn new() -> PostHistory {
+        PostHistory::default()
+    }
+
+    pub fn add_text(&mut self, post: &mut DraftPost, text: &str) {
+        self.record(&post.content);
+        post.add_text(text);
+    }
+
+    pub fn request_review(&mut self, post: DraftPost) -> PendingReviewPost {
+        self.record(&post.content);
+        post.request_review()
+    }
+
+    pub fn approve(&mut self, post: PendingReviewPost) -> Post {
+        self.record(&post.content);
+        post.approve()
+    }
+
+    // A new change starts a new branch of history, so whatever was undone before can't
+    // be redone anymore.
+    fn record(&mut self, content: &str) {
+        self.snapshots.push(String::from(content));
+        self.undone.clear();
+    }
+
+    // Gives the post back untouched when there is nothing left to undo.
+    pub fn undo<P: PostState>(&mut self, post: P) -> Result<DraftPost, P> {
+        match self.snapshots.pop() {
+            Some(content) => {
+                self.undone.push(post.into_content());
+                Ok(DraftPost { content })
+            }
+            None => Err(post),
+        }
+    }
+
+    // Gives the draft back untouched when there is nothing left to redo.
+    pub fn redo(&mut self, post: DraftPost) -> Result<DraftPost, DraftPost> {
+        match self.undone.pop() {
+            Some(content) => {
+                self.snapshots.push(post.content);
+                Ok(DraftPost { content })
+            }
+            None => Err(post),
+        }
+    }
+}
+
+// The same idea works for anything with states. A file here is just a buffer of text:
+// a ClosedFile has no read method, so reading a closed file is a compile error. Files
+// start out open, and close and reopen consume one state to give back the other.
+pub struct ClosedFile {
+    contents: String,
+}
+
+pub struct OpenFile {
+    contents: String,
+}
+
+impl ClosedFile {
+    pub fn open(contents: &str) -> OpenFile {
+        OpenFile {
+            contents: String::from(contents),
+        }
+    }
+
+    pub fn reopen(self) -> OpenFile {
+        OpenFile {
+            contents: self.contents,
+        }
+    }
+}
+
+impl OpenFile {
+    pub fn read(&self) -> &str {
+        &self.contents
+    }
+
+    pub fn close(self) -> ClosedFile {
+        ClosedFile {
+            contents: self.contents,
+        }
+    }
+}
+
+// A builder can use the same trick for required fields. The state is only a marker
+// type held in PhantomData, so it costs nothing at runtime, and build only exists once
+// the url has been set:
+//
+/// ```compile_fail
+/// use blog_rust::RequestBuilder;
+///
+/// let request = RequestBuilder::new().method("POST").build();
+/// ```
+pub struct RequestBuilder<State> {
+    url: String,
+    method: String,
+    headers: Vec<(String, String)>,
+    state: PhantomData<State>,
+}
+
+pub struct NoUrl;
+
+pub struct HasUrl;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestBuilder<NoUrl> {
+    pub fn new() -> RequestBuilder<NoUrl> {
+        RequestBuilder {
+            url: String::new(),
+            method: String::from("GET"),
+            headers: Vec::new(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn url(self, url: &str) -> RequestBuilder<HasUrl> {
+        RequestBuilder {
+            url: String::from(url),
+            method: self.method,
+            headers: self.headers,
+            state: PhantomData,
+        }
+    }
+}
+
+impl Default for RequestBuilder<NoUrl> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<State> RequestBuilder<State> {
+    pub fn method(mut self, method: &str) -> RequestBuilder<State> {
+        self.method = String::from(method);
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> RequestBuilder<State> {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+}
+
+impl RequestBuilder<HasUrl> {
+    pub fn build(self) -> Request {
+        Request {
+            url: self.url,
+            method: self.method,
+            headers: self.headers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_restores_the_draft_content() {
+        let mut history = PostHistory::new();
+
+        let mut post = Post::new();
+        post.add_text("I ate a salad");
+
+        // Go back from review to the draft and keep writing
+        let pending = history.request_review(post);
+        let mut draft = history.undo(pending).ok().unwrap();
+        assert_eq!("I ate a salad", draft.content);
+        draft.add_text(" for lunch today");
+
+        let post = history.request_review(draft);
+        let post = history.approve(post);
+        assert_eq!("I ate a salad for lunch today", post.content());
+
+        let draft = history.undo(post).ok().unwrap();
+        assert_eq!("I ate a salad for lunch today", draft.content);
+        let draft = history.undo(draft).ok().unwrap();
+        assert!(history.undo(draft).is_err());
+    }
+
+    #[test]
+    fn redo_restores_what_undo_dropped() {
+        let mut history = PostHistory::new();
+
+        let mut draft = Post::new();
+        history.add_text(&mut draft, "I ate a salad");
+        history.add_text(&mut draft, " for lunch");
+
+        let draft = history.undo(draft).ok().unwrap();
+        assert_eq!("I ate a salad", draft.content);
+        let draft = history.undo(draft).ok().unwrap();
+        assert_eq!("", draft.content);
+
+        let draft = history.redo(draft).ok().unwrap();
+        assert_eq!("I ate a salad", draft.content);
+        let draft = history.redo(draft).ok().unwrap();
+        assert_eq!("I ate a salad for lunch", draft.content);
+
+        let draft = history.redo(draft).err().unwrap();
+        assert_eq!("I ate a salad for lunch", draft.content);
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut history = PostHistory::new();
+
+        let mut draft = Post::new();
+        history.add_text(&mut draft, "I ate a salad");
+
+        let mut draft = history.undo(draft).ok().unwrap();
+        history.add_text(&mut draft, "I ate soup");
+
+        let draft = history.redo(draft).err().unwrap();
+        assert_eq!("I ate soup", draft.content);
+    }
+
+    #[test]
+    fn rejected_post_can_be_edited_and_resubmitted() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad");
+
+        let mut draft = post.request_review().reject();
+        draft.add_text(" for lunch today");
+
+        let post = draft.request_review().approve();
+        assert_eq!("I ate a salad for lunch today", post.content());
+    }
+
+    #[test]
+    fn add_text_limited_within_the_limit() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad");
+
+        assert_eq!(post.add_text_limited(" for lunch", 6), Ok(()));
+        assert_eq!("I ate a salad for lunch", post.content);
+    }
+
+    #[test]
+    fn add_text_limited_over_the_limit_keeps_content() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad");
+
+        assert_eq!(
+            post.add_text_limited(" for lunch today", 6),
+            Err(String::from("the post would have 7 words, the limit is 6"))
+        );
+        assert_eq!("I ate a salad", post.content);
+    }
+
+    #[test]
+    fn file_contents_survive_close_and_reopen() {
+        let file = ClosedFile::open("I ate a salad for lunch today");
+        assert_eq!(file.read(), "I ate a salad for lunch today");
+
+        // file.close().read() would not compile
+        let file = file.close().reopen();
+        assert_eq!(file.read(), "I ate a salad for lunch today");
+    }
+
+    #[test]
+    fn builder_builds_once_url_is_set() {
+        let request = RequestBuilder::new()
+            .method("POST")
+            .url("http://127.0.0.1:7878/posts")
+            .header("Content-Type", "text/plain")
+            .build();
+
+        assert_eq!(request.url, "http://127.0.0.1:7878/posts");
+        assert_eq!(request.method, "POST");
+        assert_eq!(
+            request.headers,
+            vec![(String::from("Content-Type"), String::from("text/plain"))]
+        );
+
+        let request = RequestBuilder::new().url("http://127.0.0.1:7878/").build();
+        assert_eq!(request.method, "GET");
+        assert!(request.headers.is_empty());
+    }
 }
@@ -1,5 +1,49 @@
+use std::collections::HashMap;
+
+// Textual rendering of a component, next to the real drawing done by Draw::draw. It's a
+// trait of its own, so components that have no text form don't have to implement it.
+pub trait DrawText {
+    fn draw_text(&self) -> String;
+}
+
 pub trait Draw {
     fn draw(&self);
+
+    // Short text form of the component: its type name and dimensions. Screen::describe
+    // joins these, which gives something stable to compare in snapshot tests. The
+    // default uses the type name without its module path, so a component only needs to
+    // override it to show more, like a label.
+    fn describe(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+        let (width, height) = self.bounds();
+        format!("{} {}x{}", name, width, height)
+    }
+
+    // Width and height the component takes up, for laying out a screen. A component
+    // that doesn't say takes up no space, so adding this method didn't break the ones
+    // that existed before it.
+    fn bounds(&self) -> (u32, u32) {
+        (0, 0)
+    }
+
+    // Whether the user can click or type into the component. Most components can, so
+    // this is a provided method that only non-interactive ones need to override.
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    // Hidden components stay on the screen but are skipped when drawing only what the
+    // user can see. Everything is visible unless it says otherwise.
+    fn is_visible(&self) -> bool {
+        true
+    }
+
+    // A trait object only gives access to Draw methods. Components that also implement
+    // DrawText return Some(self) here, so Screen::render can reach their text.
+    fn as_draw_text(&self) -> Option<&dyn DrawText> {
+        None
+    }
 }
 
 pub struct Screen {
@@ -17,18 +61,130 @@ impl Screen {
             component.draw();
         }
     }
+
+    pub fn add(&mut self, c: Box<dyn Draw>) {
+        self.components.push(c);
+    }
+
+    // None when there is no component at index
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn Draw>> {
+        if index < self.components.len() {
+            Some(self.components.remove(index))
+        } else {
+            None
+        }
+    }
+
+    // Components are drawn in order, so the last one ends up on top of the others.
+    // Moving a component to the front of the screen means moving it to the end of the
+    // list. An index out of range does nothing.
+    pub fn move_to_front(&mut self, index: usize) {
+        if let Some(component) = self.remove(index) {
+            self.components.push(component);
+        }
+    }
+
+    // Same as run, but reports how many components were drawn
+    pub fn run_counted(&self) -> usize {
+        self.run_filtered(|_| true)
+    }
+
+    // Draws only the components the predicate accepts and returns how many that was
+    pub fn run_filtered<F: Fn(&Box<dyn Draw>) -> bool>(&self, pred: F) -> usize {
+        let mut drawn = 0;
+        for component in self.components.iter().filter(|c| pred(c)) {
+            component.draw();
+            drawn += 1;
+        }
+        drawn
+    }
+
+    // Text of every component, one per line, in drawing order. Components without a
+    // text form show their description instead.
+    pub fn render(&self) -> String {
+        self.components
+            .iter()
+            .map(|component| match component.as_draw_text() {
+                Some(text) => text.draw_text(),
+                None => component.describe(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Height of the components stacked one above the other
+    pub fn total_height(&self) -> u32 {
+        self.components
+            .iter()
+            .map(|component| component.bounds().1)
+            .sum()
+    }
+
+    // One line per component, in drawing order
+    pub fn describe(&self) -> String {
+        self.components
+            .iter()
+            .map(|component| component.describe())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Positions in components of everything the user can interact with
+    pub fn clickable_indices(&self) -> Vec<usize> {
+        self.components
+            .iter()
+            .enumerate()
+            .filter(|(_, component)| component.is_interactive())
+            .map(|(index, _)| index)
+            .collect()
+    }
 }
 
 pub struct Button {
     pub width: u32,
     pub height: u32,
     pub label: String,
+    // A disabled button is still drawn (greyed out) but can't be clicked
+    pub enabled: bool,
+}
+
+impl Button {
+    pub fn new(width: u32, height: u32, label: &str) -> Button {
+        Button {
+            width,
+            height,
+            label: String::from(label),
+            enabled: true,
+        }
+    }
+}
+
+impl DrawText for Button {
+    fn draw_text(&self) -> String {
+        format!("[ {} ]", self.label)
+    }
 }
 
 impl Draw for Button {
     fn draw(&self) {
         // code to actually draw a button
     }
+
+    fn describe(&self) -> String {
+        format!("Button {}x{} {:?}", self.width, self.height, self.label)
+    }
+
+    fn bounds(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.enabled
+    }
+
+    fn as_draw_text(&self) -> Option<&dyn DrawText> {
+        Some(self)
+    }
 }
 
 pub struct TextField {
@@ -36,10 +192,403 @@ pub struct TextField {
     pub height: u32,
     pub label: String,
     pub placeholder: String,
+    // What the user typed
+    pub value: String,
+}
+
+// Counters like "120/280" count chars, not bytes: "é" is one char but two bytes in UTF-8.
+impl TextField {
+    pub fn char_count(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    pub fn is_over_limit(&self, max: usize) -> bool {
+        self.char_count() > max
+    }
+}
+
+// Shows what was typed, or the placeholder while the field is empty
+impl DrawText for TextField {
+    fn draw_text(&self) -> String {
+        let text = if self.value.is_empty() {
+            &self.placeholder
+        } else {
+            &self.value
+        };
+        format!("{}: {}", self.label, text)
+    }
 }
 
 impl Draw for TextField {
     fn draw(&self) {
         // code to actually draw a text field (can be different than that of button)
     }
+
+    fn describe(&self) -> String {
+        format!("TextField {}x{} {:?}", self.width, self.height, self.label)
+    }
+
+    fn bounds(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn as_draw_text(&self) -> Option<&dyn DrawText> {
+        Some(self)
+    }
+}
+
+pub struct Checkbox {
+    pub label: String,
+    pub checked: bool,
+}
+
+impl Checkbox {
+    pub fn new(label: &str) -> Checkbox {
+        Checkbox {
+            label: String::from(label),
+            checked: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.checked = !self.checked;
+    }
+}
+
+impl DrawText for Checkbox {
+    fn draw_text(&self) -> String {
+        let mark = if self.checked { 'x' } else { ' ' };
+        format!("[{}] {}", mark, self.label)
+    }
+}
+
+impl Draw for Checkbox {
+    fn draw(&self) {
+        // code to actually draw a checkbox
+    }
+
+    fn describe(&self) -> String {
+        let (width, height) = self.bounds();
+        format!("Checkbox {}x{} {:?}", width, height, self.label)
+    }
+
+    // A checkbox has no size of its own: it takes one line, as wide as its text
+    fn bounds(&self) -> (u32, u32) {
+        (self.draw_text().chars().count() as u32, 1)
+    }
+
+    fn as_draw_text(&self) -> Option<&dyn DrawText> {
+        Some(self)
+    }
+}
+
+// Building components by name
+// A Registry maps a name to a factory closure that creates a new trait object each time
+// it's called. This lets something like a config file describe a screen only with
+// component names, without knowing the concrete types behind them.
+type Factory = Box<dyn Fn() -> Box<dyn Draw>>;
+
+#[derive(Default)]
+pub struct Registry {
+    factories: HashMap<String, Factory>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    // Registering a name that already exists replaces its factory.
+    pub fn register(&mut self, name: &str, factory: Factory) {
+        self.factories.insert(String::from(name), factory);
+    }
+
+    pub fn create(&self, name: &str) -> Option<Box<dyn Draw>> {
+        self.factories.get(name).map(|factory| factory())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    // Mock component that counts how many times it was drawn
+    struct CountingDraw {
+        draws: Rc<Cell<usize>>,
+    }
+
+    impl Draw for CountingDraw {
+        fn draw(&self) {
+            self.draws.set(self.draws.get() + 1);
+        }
+    }
+
+    // Mock component that logs its name when drawn, to check the drawing order
+    struct NamedDraw {
+        name: &'static str,
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Draw for NamedDraw {
+        fn draw(&self) {
+            self.log.borrow_mut().push(self.name);
+        }
+
+        fn describe(&self) -> String {
+            String::from(self.name)
+        }
+    }
+
+    // Mock component that can be hidden, counting its draws like CountingDraw
+    struct HideableDraw {
+        visible: bool,
+        draws: Rc<Cell<usize>>,
+    }
+
+    impl Draw for HideableDraw {
+        fn draw(&self) {
+            self.draws.set(self.draws.get() + 1);
+        }
+
+        fn is_visible(&self) -> bool {
+            self.visible
+        }
+    }
+
+    #[test]
+    fn registry_creates_registered_component() {
+        let draws = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&draws);
+
+        let mut registry = Registry::new();
+        registry.register(
+            "button",
+            Box::new(move || {
+                Box::new(CountingDraw {
+                    draws: Rc::clone(&counter),
+                })
+            }),
+        );
+
+        let component = registry.create("button").unwrap();
+        component.draw();
+
+        assert_eq!(draws.get(), 1);
+        assert!(registry.create("slider").is_none());
+    }
+
+    fn text_field(value: &str) -> TextField {
+        TextField {
+            width: 50,
+            height: 10,
+            label: String::from("Bio"),
+            placeholder: String::from("Tell us about you"),
+            value: String::from(value),
+        }
+    }
+
+    #[test]
+    fn text_field_empty_value() {
+        let field = text_field("");
+
+        assert_eq!(field.char_count(), 0);
+        assert!(!field.is_over_limit(0));
+    }
+
+    #[test]
+    fn text_field_under_limit() {
+        let field = text_field("hello");
+
+        assert_eq!(field.char_count(), 5);
+        assert!(!field.is_over_limit(5));
+    }
+
+    #[test]
+    fn text_field_over_limit_counts_chars() {
+        // 6 chars but 7 bytes
+        let field = text_field("héllo!");
+
+        assert_eq!(field.char_count(), 6);
+        assert!(field.is_over_limit(5));
+        assert!(!field.is_over_limit(6));
+    }
+
+    #[test]
+    fn disabled_button_is_not_clickable() {
+        let mut disabled = Button::new(50, 10, "Delete");
+        disabled.enabled = false;
+
+        let screen = Screen {
+            components: vec![
+                Box::new(Button::new(50, 10, "OK")),
+                Box::new(disabled),
+                Box::new(text_field("")),
+            ],
+        };
+
+        assert_eq!(screen.clickable_indices(), vec![0, 2]);
+    }
+
+    #[test]
+    fn screen_describes_each_component() {
+        let screen = Screen {
+            components: vec![
+                Box::new(Button::new(50, 10, "OK")),
+                Box::new(text_field("")),
+            ],
+        };
+
+        assert_eq!(
+            screen.describe(),
+            "Button 50x10 \"OK\"\nTextField 50x10 \"Bio\""
+        );
+    }
+
+    #[test]
+    fn run_filtered_draws_only_visible_components() {
+        let draws = Rc::new(Cell::new(0));
+        let hideable = |visible| {
+            Box::new(HideableDraw {
+                visible,
+                draws: Rc::clone(&draws),
+            })
+        };
+
+        let screen = Screen {
+            components: vec![hideable(true), hideable(false), hideable(true)],
+        };
+
+        assert_eq!(screen.run_filtered(|c| c.is_visible()), 2);
+        assert_eq!(draws.get(), 2);
+        assert_eq!(screen.run_counted(), 3);
+        assert_eq!(draws.get(), 5);
+    }
+
+    fn named_screen(log: &Rc<RefCell<Vec<&'static str>>>) -> Screen {
+        let mut screen = Screen { components: vec![] };
+        for name in ["background", "form", "dialog"] {
+            screen.add(Box::new(NamedDraw {
+                name,
+                log: Rc::clone(log),
+            }));
+        }
+        screen
+    }
+
+    #[test]
+    fn move_to_front_draws_component_last() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut screen = named_screen(&log);
+
+        screen.move_to_front(0);
+        screen.move_to_front(10);
+        screen.run();
+
+        assert_eq!(*log.borrow(), vec!["form", "dialog", "background"]);
+    }
+
+    #[test]
+    fn describe_defaults_to_type_name_and_bounds() {
+        let component = CountingDraw {
+            draws: Rc::new(Cell::new(0)),
+        };
+
+        assert_eq!(component.describe(), "CountingDraw 0x0");
+    }
+
+    #[test]
+    fn remove_component_by_index() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut screen = named_screen(&log);
+
+        let removed = screen.remove(1).unwrap();
+        assert_eq!(removed.describe(), "form");
+        assert!(screen.remove(2).is_none());
+
+        screen.run();
+        assert_eq!(*log.borrow(), vec!["background", "dialog"]);
+    }
+
+    #[test]
+    fn total_height_of_stacked_components() {
+        let screen = Screen {
+            components: vec![
+                Box::new(Button::new(50, 10, "OK")),
+                Box::new(text_field("")),
+            ],
+        };
+
+        assert_eq!(screen.components[0].bounds(), (50, 10));
+        assert_eq!(screen.total_height(), 20);
+    }
+
+    #[test]
+    fn components_without_bounds_take_no_space() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut screen = named_screen(&log);
+        assert_eq!(screen.total_height(), 0);
+
+        screen.add(Box::new(Button::new(50, 10, "OK")));
+        assert_eq!(screen.total_height(), 10);
+    }
+
+    #[test]
+    fn render_joins_component_text() {
+        let mut filled = text_field("Ferris");
+        filled.label = String::from("Name");
+
+        let screen = Screen {
+            components: vec![
+                Box::new(text_field("")),
+                Box::new(filled),
+                Box::new(Button::new(50, 10, "OK")),
+            ],
+        };
+
+        assert_eq!(
+            screen.render(),
+            "Bio: Tell us about you\nName: Ferris\n[ OK ]"
+        );
+    }
+
+    #[test]
+    fn render_describes_components_without_text() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut screen = named_screen(&log);
+        screen.add(Box::new(Button::new(50, 10, "OK")));
+
+        assert_eq!(screen.render(), "background\nform\ndialog\n[ OK ]");
+    }
+
+    #[test]
+    fn checkbox_toggle() {
+        let mut checkbox = Checkbox::new("Remember me");
+        assert_eq!(checkbox.draw_text(), "[ ] Remember me");
+
+        checkbox.toggle();
+        assert!(checkbox.checked);
+        assert_eq!(checkbox.draw_text(), "[x] Remember me");
+
+        checkbox.toggle();
+        assert!(!checkbox.checked);
+    }
+
+    #[test]
+    fn screen_with_a_checkbox() {
+        let mut checkbox = Checkbox::new("Subscribe");
+        checkbox.toggle();
+
+        let screen = Screen {
+            components: vec![Box::new(checkbox), Box::new(Button::new(50, 10, "OK"))],
+        };
+
+        assert_eq!(screen.render(), "[x] Subscribe\n[ OK ]");
+        assert_eq!(
+            screen.describe(),
+            "Checkbox 13x1 \"Subscribe\"\nButton 50x10 \"OK\""
+        );
+        assert_eq!(screen.clickable_indices(), vec![0, 1]);
+    }
 }
@@ -29,7 +29,7 @@ compile time. It needs to figure them out at runtime.
 
 */
 
-use gui::{Button, Draw, Screen};
+use gui::{Button, Draw, DrawText, Screen};
 
 fn main() {
     let screen = Screen {
@@ -43,15 +43,14 @@ fn main() {
                     String::from("No"),
                 ],
             }),
-            Box::new(Button {
-                width: 50,
-                height: 10,
-                label: String::from("OK"),
-            }),
+            Box::new(Button::new(50, 10, "OK")),
         ],
     };
 
     screen.run();
+    println!("{}", screen.describe());
+    println!("{}", screen.render());
+    println!("Clickable components: {:?}", screen.clickable_indices());
 
     // Below will error because String doesn’t implement the Draw trait
     // let screen = Screen {
@@ -66,8 +65,31 @@ struct SelectBox {
     options: Vec<String>,
 }
 
+impl DrawText for SelectBox {
+    fn draw_text(&self) -> String {
+        format!("<{}>", self.options.join(" | "))
+    }
+}
+
 impl Draw for SelectBox {
     fn draw(&self) {
         // code to actually draw a select box
     }
+
+    fn describe(&self) -> String {
+        format!(
+            "SelectBox {}x{} ({} options)",
+            self.width,
+            self.height,
+            self.options.len()
+        )
+    }
+
+    fn bounds(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn as_draw_text(&self) -> Option<&dyn DrawText> {
+        Some(self)
+    }
 }
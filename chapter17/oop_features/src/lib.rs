@@ -36,8 +36,111 @@ impl AveragedCollection {
         self.average
     }
 
+    // Values present in exactly one of the two collections. Both lists are treated as
+    // multisets: if a value appears three times here and once in other, it shows up twice
+    // in the result. The result is sorted in ascending order.
+    pub fn symmetric_difference(&self, other: &AveragedCollection) -> Vec<i32> {
+        let mut left = self.list.clone();
+        let mut right = other.list.clone();
+        left.sort();
+        right.sort();
+
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() && j < right.len() {
+            if left[i] < right[j] {
+                result.push(left[i]);
+                i += 1;
+            } else if left[i] > right[j] {
+                result.push(right[j]);
+                j += 1;
+            } else {
+                i += 1;
+                j += 1;
+            }
+        }
+        result.extend_from_slice(&left[i..]);
+        result.extend_from_slice(&right[j..]);
+
+        result
+    }
+
+    // An empty list has no average; 0.0 stands in for it rather than the NaN that 0.0 / 0.0
+    // would give, which compares unequal even to itself.
     fn update_average(&mut self) {
         let total: i32 = self.list.iter().sum();
-        self.average = total as f64 / self.list.len() as f64;
+        self.average = if self.list.is_empty() {
+            0.0
+        } else {
+            total as f64 / self.list.len() as f64
+        };
+    }
+}
+
+// Building a collection from a whole vector at once: the list is moved in and the
+// average is computed a single time instead of once per add.
+impl From<Vec<i32>> for AveragedCollection {
+    fn from(list: Vec<i32>) -> AveragedCollection {
+        let mut collection = AveragedCollection { list, average: 0.0 };
+        collection.update_average();
+        collection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(values: &[i32]) -> AveragedCollection {
+        let mut collection = AveragedCollection {
+            list: vec![],
+            average: 0.0,
+        };
+        for value in values {
+            collection.add(*value);
+        }
+        collection
+    }
+
+    #[test]
+    fn symmetric_difference_of_disjoint_collections() {
+        let a = collection(&[3, 1]);
+        let b = collection(&[4, 2]);
+
+        assert_eq!(a.symmetric_difference(&b), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn symmetric_difference_of_overlapping_collections() {
+        let a = collection(&[1, 2, 2, 3]);
+        let b = collection(&[3, 2, 1, 2]);
+
+        assert_eq!(a.symmetric_difference(&b), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn symmetric_difference_of_partial_overlap() {
+        let a = collection(&[1, 2, 2, 5]);
+        let b = collection(&[2, 3, 5]);
+
+        assert_eq!(a.symmetric_difference(&b), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn from_vec_matches_repeated_add() {
+        let from_vec = AveragedCollection::from(vec![4, 8, 15, 16, 23, 42]);
+        let added = collection(&[4, 8, 15, 16, 23, 42]);
+
+        assert_eq!(from_vec.average(), added.average());
+        assert_eq!(from_vec.average(), 18.0);
+    }
+
+    #[test]
+    fn empty_collection_averages_zero() {
+        assert_eq!(AveragedCollection::from(vec![]).average(), 0.0);
+
+        let mut emptied = collection(&[7]);
+        assert_eq!(emptied.remove(), Some(7));
+        assert_eq!(emptied.average(), 0.0);
     }
 }
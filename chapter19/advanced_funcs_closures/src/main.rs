@@ -26,6 +26,10 @@ Rust doesn’t know how much space it will need to store the closure. (trait obj
 fn main() {
     let answer = do_twice(add_one, 5);
     println!("The answer is: {}", answer);
+    // A closure capturing a local only works with the generic version
+    let step = 3;
+    let answer = do_twice_fn(|x| x * step, 5);
+    println!("The answer with a closure is: {}", answer);
     // Closure
     let list_of_numbers = vec![1, 2, 3];
     let _list_of_strings: Vec<String> = list_of_numbers.iter().map(|i| i.to_string()).collect();
@@ -34,6 +38,19 @@ fn main() {
     let _list_of_strings: Vec<String> = list_of_numbers.iter().map(ToString::to_string).collect();
     // Or enums as initializer function
     let _list_of_statuses: Vec<Status> = (0u32..20).map(Status::Value).collect();
+
+    // Picking a closure at runtime
+    let ops = [
+        make_op(Op::Add(2)),
+        make_op(Op::Mul(10)),
+        make_op(Op::Sub(1)),
+    ];
+    let result = ops.iter().fold(1, |acc, op| op(acc));
+    println!("((1 + 2) * 10) - 1 = {}", result);
+
+    let mut total = make_accumulator();
+    total(5);
+    println!("Running total: {}", total(7));
 }
 
 fn add_one(x: i32) -> i32 {
@@ -44,6 +61,13 @@ fn do_twice(f: fn(i32) -> i32, arg: i32) -> i32 {
     f(arg) + f(arg)
 }
 
+// Generic over the Fn trait, so it takes functions and closures alike, including closures
+// that capture their environment (those can't coerce to fn). The fn version above is still
+// what we'd use when talking to C code.
+fn do_twice_fn<F: Fn(i32) -> i32>(f: F, arg: i32) -> i32 {
+    f(arg) + f(arg)
+}
+
 enum Status {
     Value(u32),
     Stop,
@@ -53,3 +77,63 @@ enum Status {
 fn returns_closure() -> Box<dyn Fn(i32) -> i32> {
     Box::new(|x| x + 1)
 }
+
+// Each arm returns a different closure, and every closure has its own anonymous type, so
+// they can only share a return type as trait objects. move copies the operand into the
+// closure, which has to outlive make_op.
+enum Op {
+    Add(i32),
+    Mul(i32),
+    Sub(i32),
+}
+
+fn make_op(op: Op) -> Box<dyn Fn(i32) -> i32> {
+    match op {
+        Op::Add(n) => Box::new(move |x| x + n),
+        Op::Mul(n) => Box::new(move |x| x * n),
+        Op::Sub(n) => Box::new(move |x| x - n),
+    }
+}
+
+// Returning a closure with impl Trait: there's only one closure type here, so it doesn't
+// need a Box. The closure owns total (moved in), and since calling it changes total, it
+// implements FnMut and the caller has to keep it in a mut binding.
+fn make_accumulator() -> impl FnMut(i32) -> i32 {
+    let mut total = 0;
+    move |x| {
+        total += x;
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn do_twice_fn_with_capturing_closure() {
+        let multiplier = 4;
+
+        assert_eq!(do_twice_fn(|x| x * multiplier, 5), 40);
+        assert_eq!(do_twice_fn(add_one, 5), do_twice(add_one, 5));
+    }
+
+    #[test]
+    fn make_op_for_each_variant() {
+        assert_eq!(make_op(Op::Add(3))(7), 10);
+        assert_eq!(make_op(Op::Mul(3))(7), 21);
+        assert_eq!(make_op(Op::Sub(3))(7), 4);
+    }
+
+    #[test]
+    fn accumulator_keeps_a_running_sum() {
+        let mut add = make_accumulator();
+
+        assert_eq!(add(1), 1);
+        assert_eq!(add(2), 3);
+        assert_eq!(add(-4), -1);
+
+        // A new accumulator starts from zero again
+        assert_eq!(make_accumulator()(10), 10);
+    }
+}
@@ -64,6 +64,11 @@ fn main() {
         Point { x: 1, y: 0 } + Point { x: 2, y: 3 },
         Point { x: 3, y: 3 }
     );
+    println!("from slice: {:?}", Point::try_from(&[4, 2][..]));
+    println!(
+        "saturating: {:?}",
+        Point { x: i32::MAX, y: 1 }.saturating_add(Point { x: 1, y: 1 })
+    );
 
     // Traits with methods using the same name
     // Specifying which trait’s fly method we want to call.
@@ -109,6 +114,34 @@ impl Add for Point {
     }
 }
 
+// The + above overflows like plain i32 addition: a panic in debug builds, wrapping around
+// in release builds. For coordinates coming from untrusted input, saturating_add stops
+// each component at i32::MAX or i32::MIN instead.
+impl Point {
+    fn saturating_add(self, other: Point) -> Point {
+        Point {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+        }
+    }
+}
+
+// Conversions that can fail implement TryFrom instead of From. A slice only makes a
+// Point when it holds exactly two values, x then y; any other length is an error.
+impl TryFrom<&[i32]> for Point {
+    type Error = String;
+
+    fn try_from(values: &[i32]) -> Result<Point, String> {
+        match values {
+            [x, y] => Ok(Point { x: *x, y: *y }),
+            _ => Err(format!(
+                "a Point needs exactly 2 values, got {}",
+                values.len()
+            )),
+        }
+    }
+}
+
 // Here we define a new generic type parameter
 // We want to add values in millimeters to values in meters and have the
 // implementation of Add do the conversion correctly. We can implement Add for
@@ -207,3 +240,48 @@ impl fmt::Display for Wrapper {
         write!(f, "[{}]", self.0.join(", "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_from_two_values() {
+        assert_eq!(Point::try_from(&[1, -2][..]), Ok(Point { x: 1, y: -2 }));
+    }
+
+    #[test]
+    fn point_from_wrong_length() {
+        assert_eq!(
+            Point::try_from(&[1][..]),
+            Err(String::from("a Point needs exactly 2 values, got 1"))
+        );
+        assert_eq!(
+            Point::try_from(&[1, 2, 3][..]),
+            Err(String::from("a Point needs exactly 2 values, got 3"))
+        );
+    }
+
+    #[test]
+    fn saturating_add_stops_at_the_limits() {
+        let near_max = Point {
+            x: i32::MAX - 1,
+            y: i32::MIN + 1,
+        };
+
+        assert_eq!(
+            near_max.saturating_add(Point { x: 10, y: -10 }),
+            Point {
+                x: i32::MAX,
+                y: i32::MIN
+            }
+        );
+        assert_eq!(
+            near_max.saturating_add(Point { x: 1, y: 0 }),
+            Point {
+                x: i32::MAX,
+                y: i32::MIN + 1
+            }
+        );
+    }
+}
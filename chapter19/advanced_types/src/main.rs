@@ -74,16 +74,25 @@ fn main() {
 
     let _f: Thunk = Box::new(|| println!("hi"));
 
+    // Result2<T> in a real signature
+    let mut buf = Vec::new();
+    let written = write_line(&mut buf, "hello").unwrap();
+    println!("wrote {} bytes", written);
+
     // Never types
     // Inside a for loop, within a match statement (only single type return allowed)
     // let guess: u32 = match guess.trim().parse() {
     //     Ok(num) => num,
     //     Err(_) => continue, // the never type continue can be combined here
     // };
+    println!("parsed = {:?}", parse_or_skip(&["1", "two", "3"]));
 
     // Dynamically sized types (str): the following is not allowed
     // let s1: str = "Hello there!";
     // let s2: str = "How's it going?";
+    // But behind a reference, str works with a ?Sized generic
+    print_len("Hello there!");
+    print_len(&String::from("How's it going?"));
 }
 
 // Consider this trait:
@@ -104,3 +113,67 @@ pub trait Write2 {
     fn write_all(&mut self, buf: &[u8]) -> Result2<()>;
     fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result2<()>;
 }
+
+// The alias in use: an in-memory version of writing a line, following the Write2
+// signatures. The line is appended to the buffer followed by a newline, and the number of
+// bytes written (newline included) is returned.
+fn write_line(buf: &mut Vec<u8>, line: &str) -> Result2<usize> {
+    buf.extend_from_slice(line.as_bytes());
+    buf.push(b'\n');
+    Ok(line.len() + 1)
+}
+
+// The never type in a loop: the Err arm evaluates to continue, which has type !, so the
+// match as a whole has the type of the Ok arm (i32) and unparseable tokens are skipped.
+fn parse_or_skip(tokens: &[&str]) -> Vec<i32> {
+    let mut values = Vec::new();
+
+    for token in tokens {
+        let value: i32 = match token.trim().parse() {
+            Ok(num) => num,
+            Err(_) => continue,
+        };
+        values.push(value);
+    }
+
+    values
+}
+
+// Generic functions get an implicit T: Sized bound. ?Sized relaxes it ("T may or may not
+// be Sized"), and since T might not be sized the parameter has to be behind a pointer,
+// here &T. This way T can be str itself, and not only String or &str.
+fn print_len<T: ?Sized + AsRef<str>>(value: &T) -> usize {
+    let len = value.as_ref().len();
+    println!("length = {}", len);
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_line_appends_to_the_buffer() {
+        let mut buf = b"first\n".to_vec();
+
+        let written = write_line(&mut buf, "second").unwrap();
+
+        assert_eq!(written, 7);
+        assert_eq!(buf, b"first\nsecond\n");
+    }
+
+    #[test]
+    fn parse_or_skip_keeps_only_valid_integers() {
+        let tokens = ["4", "four", " 8 ", "", "-15", "1.5"];
+
+        assert_eq!(parse_or_skip(&tokens), vec![4, 8, -15]);
+    }
+
+    #[test]
+    fn print_len_accepts_str_and_string() {
+        // T = str, an unsized type
+        assert_eq!(print_len("Hello there!"), 12);
+        // T = String
+        assert_eq!(print_len(&String::from("How's it going?")), 15);
+    }
+}
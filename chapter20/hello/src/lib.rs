@@ -0,0 +1,521 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, Write},
+    path::Path,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// A fixed number of worker threads that run jobs sent to them.
+///
+/// The pool keeps the sending end of a channel; every worker shares the receiving end
+/// through an `Arc<Mutex<_>>`, so each job is picked up by exactly one worker, whichever
+/// is free first.
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    // Only None while the pool is being dropped
+    sender: Option<mpsc::Sender<Job>>,
+}
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+impl ThreadPool {
+    /// Create a new ThreadPool.
+    ///
+    /// The size is the number of threads in the pool.
+    ///
+    /// # Panics
+    ///
+    /// The `new` function will panic if the size is zero.
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0);
+
+        let (sender, receiver) = mpsc::channel();
+
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let mut workers = Vec::with_capacity(size);
+
+        for id in 0..size {
+            workers.push(Worker::new(id, Arc::clone(&receiver)));
+        }
+
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    /// Run `f` on the next free worker.
+    ///
+    /// The bounds are the same as `thread::spawn`: the closure runs once, on another
+    /// thread, and may outlive the caller.
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job = Box::new(f);
+
+        self.sender.as_ref().unwrap().send(job).unwrap();
+    }
+
+    /// Number of worker threads in the pool.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+/// Graceful shutdown: dropping the sender closes the channel, which is the signal for
+/// every worker to stop once the queue is empty. Joining each thread then waits for the
+/// jobs already sent, so no work is lost when the pool goes away.
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            println!("Shutting down worker {}", worker.id);
+
+            // take leaves None behind, since join needs to own the handle
+            if let Some(thread) = worker.thread.take() {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+struct Worker {
+    id: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    // A worker waits for a job, runs it, and goes back to waiting until the channel is
+    // closed.
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
+        // The lock is released at the end of the let statement, so other workers can
+        // wait for the next job while this one runs.
+        let thread = thread::spawn(move || loop {
+            let message = receiver.lock().unwrap().recv();
+
+            match message {
+                Ok(job) => {
+                    println!("Worker {id} got a job; executing.");
+
+                    job();
+                }
+                Err(_) => {
+                    println!("Worker {id} disconnected; shutting down.");
+                    break;
+                }
+            }
+        });
+
+        Worker {
+            id,
+            thread: Some(thread),
+        }
+    }
+}
+
+/// Maps request paths to the files served for them.
+///
+/// Only GET is supported: any other method on any path gets a 405, and a GET for a path
+/// that was never registered gets the 404 page.
+#[derive(Default)]
+pub struct Router {
+    routes: HashMap<String, String>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router::default()
+    }
+
+    /// Serve `filename` for GET requests to `path`.
+    pub fn get(&mut self, path: &str, filename: &str) {
+        self.routes
+            .insert(String::from(path), String::from(filename));
+    }
+
+    /// Status line and file to send back for a request line such as
+    /// `GET /about HTTP/1.1`.
+    ///
+    /// Only the method and the path are looked at, so the HTTP version doesn't matter, and
+    /// a query string doesn't change the route. A line without a path is treated like an
+    /// unknown route.
+    pub fn resolve(&self, request_line: &str) -> (&str, &str) {
+        let mut parts = request_line.split_whitespace();
+
+        match (parts.next(), parts.next()) {
+            (Some("GET"), Some(path)) => match self.routes.get(parse_query(path).0) {
+                Some(filename) => ("HTTP/1.1 200 OK", filename),
+                None => ("HTTP/1.1 404 NOT FOUND", "404.html"),
+            },
+            (Some(_), Some(_)) => ("HTTP/1.1 405 METHOD NOT ALLOWED", "405.html"),
+            _ => ("HTTP/1.1 404 NOT FOUND", "404.html"),
+        }
+    }
+}
+
+/// The parts of an HTTP request the server looks at.
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub version: String,
+    /// Header names are lowercase, so a lookup doesn't depend on how the client wrote them.
+    pub headers: HashMap<String, String>,
+}
+
+impl Request {
+    /// The request line rebuilt from its parts, e.g. `GET / HTTP/1.1`.
+    pub fn line(&self) -> String {
+        format!("{} {} {}", self.method, self.path, self.version)
+    }
+}
+
+/// Read the request line and the headers, up to the blank line that ends them.
+///
+/// The body, if any, is left in the reader. Parts missing from the request line are
+/// empty strings, and header lines without a `:` are skipped. Reading also stops at the
+/// end of the input or on a read error.
+pub fn read_request(reader: &mut impl BufRead) -> Request {
+    let mut lines = reader.lines().map_while(Result::ok);
+
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let mut next_part = || String::from(parts.next().unwrap_or(""));
+    let (method, path, version) = (next_part(), next_part(), next_part());
+
+    let mut headers = HashMap::new();
+    for line in lines.take_while(|line| !line.is_empty()) {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), String::from(value.trim()));
+        }
+    }
+
+    Request {
+        method,
+        path,
+        version,
+        headers,
+    }
+}
+
+/// Write one log line for a request that was answered, as
+/// `{method} {path} -> {status} ({ms}ms)`.
+///
+/// The target is any writer, so the server can log to stdout and a test can log into a
+/// `Vec<u8>`. The duration is rounded down to whole milliseconds.
+pub fn log_request(
+    out: &mut dyn Write,
+    method: &str,
+    path: &str,
+    status: &str,
+    duration: Duration,
+) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{method} {path} -> {status} ({}ms)",
+        duration.as_millis()
+    )
+}
+
+/// Split a request path such as `/greet?name=Ada` into the bare path and its query
+/// parameters.
+///
+/// Only the basic encodings of a space are decoded: `+` and `%20`. A parameter without an
+/// `=` gets an empty value, and when a name is repeated the last value wins. A missing or
+/// empty query string gives an empty map.
+pub fn parse_query(path: &str) -> (&str, HashMap<String, String>) {
+    let mut params = HashMap::new();
+
+    let (path, query) = match path.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (path, ""),
+    };
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        params.insert(decode(name), decode(value));
+    }
+
+    (path, params)
+}
+
+fn decode(s: &str) -> String {
+    s.replace('+', " ").replace("%20", " ")
+}
+
+/// Response for a request for the file at `path` under `root`.
+///
+/// Returns the head of the response (status line, `Content-Type` and `Content-Length`,
+/// without the blank line that ends it) and the body. The content type comes from the
+/// file extension. A path containing `..` could reach outside `root`, so it gets a 403
+/// without touching the file system; a file that can't be read gets a 404.
+pub fn serve_static(path: &str, root: &Path) -> (String, Vec<u8>) {
+    let (path, _) = parse_query(path);
+
+    if path.contains("..") {
+        return plain_response("HTTP/1.1 403 FORBIDDEN", "Forbidden");
+    }
+
+    match fs::read(root.join(path.trim_start_matches('/'))) {
+        Ok(body) => {
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}",
+                content_type(path),
+                body.len()
+            );
+            (head, body)
+        }
+        Err(_) => plain_response("HTTP/1.1 404 NOT FOUND", "Not Found"),
+    }
+}
+
+fn content_type(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("png") => "image/png",
+        _ => "application/octet-stream",
+    }
+}
+
+fn plain_response(status_line: &str, body: &str) -> (String, Vec<u8>) {
+    let head = format!(
+        "{status_line}\r\nContent-Type: text/plain\r\nContent-Length: {}",
+        body.len()
+    );
+    (head, body.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_runs_every_job() {
+        let pool = ThreadPool::new(4);
+        let finished = Arc::new(Mutex::new(Vec::new()));
+        let (done_tx, done_rx) = mpsc::channel();
+
+        for job in 0..8 {
+            let finished = Arc::clone(&finished);
+            let done_tx = done_tx.clone();
+            pool.execute(move || {
+                finished.lock().unwrap().push(job);
+                done_tx.send(()).unwrap();
+            });
+        }
+        for _ in 0..8 {
+            done_rx.recv().unwrap();
+        }
+
+        let mut finished = finished.lock().unwrap().clone();
+        finished.sort();
+        assert_eq!(pool.size(), 4);
+        assert_eq!(finished, (0..8).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn dropping_the_pool_finishes_queued_jobs() {
+        let finished = Arc::new(Mutex::new(Vec::new()));
+
+        let pool = ThreadPool::new(2);
+        for job in 0..6 {
+            let finished = Arc::clone(&finished);
+            pool.execute(move || {
+                thread::sleep(std::time::Duration::from_millis(10));
+                finished.lock().unwrap().push(job);
+            });
+        }
+        drop(pool);
+
+        let mut finished = finished.lock().unwrap().clone();
+        finished.sort();
+        assert_eq!(finished, (0..6).collect::<Vec<usize>>());
+    }
+
+    fn router() -> Router {
+        let mut router = Router::new();
+        router.get("/", "hello.html");
+        router.get("/about", "about.html");
+        router
+    }
+
+    #[test]
+    fn router_serves_registered_route() {
+        let router = router();
+
+        assert_eq!(
+            router.resolve("GET /about HTTP/1.1"),
+            ("HTTP/1.1 200 OK", "about.html")
+        );
+        // The version is not part of the match
+        assert_eq!(
+            router.resolve("GET / HTTP/2"),
+            ("HTTP/1.1 200 OK", "hello.html")
+        );
+    }
+
+    #[test]
+    fn router_ignores_query_string() {
+        assert_eq!(
+            router().resolve("GET /about?lang=en HTTP/1.1"),
+            ("HTTP/1.1 200 OK", "about.html")
+        );
+    }
+
+    #[test]
+    fn router_unknown_route_is_not_found() {
+        assert_eq!(
+            router().resolve("GET /missing HTTP/1.1"),
+            ("HTTP/1.1 404 NOT FOUND", "404.html")
+        );
+    }
+
+    #[test]
+    fn router_rejects_other_methods() {
+        assert_eq!(
+            router().resolve("POST /about HTTP/1.1"),
+            ("HTTP/1.1 405 METHOD NOT ALLOWED", "405.html")
+        );
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (String::from(*name), String::from(*value)))
+            .collect()
+    }
+
+    #[test]
+    fn parse_query_single_param() {
+        assert_eq!(
+            parse_query("/greet?name=Ada"),
+            ("/greet", params(&[("name", "Ada")]))
+        );
+    }
+
+    #[test]
+    fn parse_query_multiple_params() {
+        assert_eq!(
+            parse_query("/greet?name=Ada&lang=en&formal"),
+            (
+                "/greet",
+                params(&[("name", "Ada"), ("lang", "en"), ("formal", "")])
+            )
+        );
+    }
+
+    #[test]
+    fn parse_query_decodes_spaces() {
+        assert_eq!(
+            parse_query("/greet?name=Ada+Lovelace&title=Countess%20of%20Lovelace"),
+            (
+                "/greet",
+                params(&[("name", "Ada Lovelace"), ("title", "Countess of Lovelace")])
+            )
+        );
+    }
+
+    #[test]
+    fn parse_query_without_query() {
+        assert_eq!(parse_query("/greet"), ("/greet", HashMap::new()));
+        assert_eq!(parse_query("/greet?"), ("/greet", HashMap::new()));
+    }
+
+    // A fresh directory under the system temp dir with the given files in it
+    fn static_root(name: &str, files: &[(&str, &[u8])]) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(name);
+        fs::create_dir_all(&root).unwrap();
+        for (file, contents) in files {
+            fs::write(root.join(file), contents).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn serve_static_html_file() {
+        let root = static_root(
+            "hello_serve_static_html",
+            &[("about.html", b"<h1>About</h1>")],
+        );
+
+        let (head, body) = serve_static("/about.html?lang=en", &root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            head,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 14"
+        );
+        assert_eq!(body, b"<h1>About</h1>");
+    }
+
+    #[test]
+    fn serve_static_png_file() {
+        let png: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        let root = static_root("hello_serve_static_png", &[("logo.png", png)]);
+
+        let (head, body) = serve_static("/logo.png", &root);
+        let (missing, _) = serve_static("/missing.png", &root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(head.contains("Content-Type: image/png\r\n"));
+        assert_eq!(body, png);
+        assert!(missing.starts_with("HTTP/1.1 404 NOT FOUND\r\n"));
+    }
+
+    #[test]
+    fn serve_static_rejects_traversal() {
+        let root = static_root("hello_serve_static_traversal", &[]);
+
+        let (head, body) = serve_static("/../hello.html", &root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(head.starts_with("HTTP/1.1 403 FORBIDDEN\r\n"));
+        assert_eq!(body, b"Forbidden");
+    }
+
+    #[test]
+    fn read_request_parses_line_and_headers() {
+        let raw: &[u8] = b"GET /about HTTP/1.1\r\n\
+            Host: 127.0.0.1:7878\r\n\
+            User-Agent: curl/8.0\r\n\
+            \r\n\
+            body is not read";
+        let mut reader = std::io::Cursor::new(raw);
+
+        let request = read_request(&mut reader);
+
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/about");
+        assert_eq!(request.version, "HTTP/1.1");
+        assert_eq!(request.headers["host"], "127.0.0.1:7878");
+        assert_eq!(request.headers["user-agent"], "curl/8.0");
+        assert_eq!(request.line(), "GET /about HTTP/1.1");
+    }
+
+    #[test]
+    fn log_request_formats_one_line() {
+        let mut out: Vec<u8> = Vec::new();
+
+        log_request(
+            &mut out,
+            "GET",
+            "/about",
+            "200",
+            Duration::from_micros(12_750),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "GET /about -> 200 (12ms)\n"
+        );
+    }
+}
@@ -17,40 +17,84 @@ HTTP requests and responses.
 
 // Get access to traits and types
 use std::{
-    fs,
-    io::{prelude::*, BufReader},
+    env, fs,
+    io::{self, prelude::*, BufReader},
     net::{TcpListener, TcpStream},
+    path::Path,
+    sync::Arc,
+    time::Instant,
 };
 
+use hello::{log_request, read_request, serve_static, Router, ThreadPool};
+
 fn main() {
     // Listening to the TCP Connection
     let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+    // Each connection is handled on one of four threads, so a slow request doesn't hold
+    // up the ones behind it.
+    let pool = ThreadPool::new(4);
+
+    // The routes are shared by every worker, read-only, so an Arc is enough.
+    let mut router = Router::new();
+    router.get("/", "hello.html");
+    let router = Arc::new(router);
 
-    for stream in listener.incoming() {
+    // An optional argument stops the server after that many connections, which is handy
+    // for trying out the shutdown. Without it the server runs until it is killed.
+    let max_connections = env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(usize::MAX);
+
+    for stream in listener.incoming().take(max_connections) {
         let stream = stream.unwrap();
 
-        handle_connection(stream);
+        let router = Arc::clone(&router);
+
+        pool.execute(move || handle_connection(stream, &router));
     }
 
+    // The pool is dropped at the end of main, which waits for the requests in flight.
+    println!("Shutting down.");
+
     // Reading the request
 }
 
 /// Read data from the TCP stream and print it so we can see the data being sent
 /// from the browser.
-fn handle_connection(mut stream: TcpStream) {
-    let buf_reader = BufReader::new(&mut stream);
-    let request_line = buf_reader.lines().next().unwrap().unwrap();
-
-    let (status_line, filename) = if request_line == "GET / HTTP/1.1" {
-        ("HTTP/1.1 200 OK", "hello.html")
-    } else {
-        ("HTTP/1.1 404 NOT FOUND", "404.html")
-    };
+fn handle_connection(mut stream: TcpStream, router: &Router) {
+    let start = Instant::now();
+    let mut buf_reader = BufReader::new(&mut stream);
+    let request = read_request(&mut buf_reader);
 
-    let contents = fs::read_to_string(filename).unwrap();
-    let length = contents.len();
+    // Anything under /static/ is a file from the static directory; everything else goes
+    // through the router. Bodies are bytes since a static file may not be text.
+    let (head, body) = match request.path.strip_prefix("/static/") {
+        Some(file) if request.method == "GET" => serve_static(file, Path::new("static")),
+        _ => {
+            let (status_line, filename) = router.resolve(&request.line());
+            let contents = fs::read(filename).unwrap();
+            let length = contents.len();
+            (
+                format!("{status_line}\r\nContent-Length: {length}"),
+                contents,
+            )
+        }
+    };
 
-    let response = format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}");
+    stream
+        .write_all(format!("{head}\r\n\r\n").as_bytes())
+        .unwrap();
+    stream.write_all(&body).unwrap();
 
-    stream.write_all(response.as_bytes()).unwrap();
+    // The status code is the second word of the status line
+    let status = head.split_whitespace().nth(1).unwrap_or("");
+    log_request(
+        &mut io::stdout(),
+        &request.method,
+        &request.path,
+        status,
+        start.elapsed(),
+    )
+    .unwrap();
 }
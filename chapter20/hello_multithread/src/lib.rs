@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     sync::{mpsc, Arc, Mutex},
     thread,
 };
@@ -90,3 +91,58 @@ impl Worker {
         }
     }
 }
+
+/// Parse the `cookie` header into a map of cookie names to values.
+///
+/// Header names are expected in lowercase. The header looks like
+/// `cookie: session=abc123; theme=dark`; pairs without an `=` are skipped, and a
+/// missing header gives an empty map.
+pub fn parse_cookies(headers: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut cookies = HashMap::new();
+
+    if let Some(header) = headers.get("cookie") {
+        for pair in header.split("; ") {
+            if let Some((name, value)) = pair.split_once('=') {
+                cookies.insert(String::from(name.trim()), String::from(value.trim()));
+            }
+        }
+    }
+
+    cookies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(cookie: Option<&str>) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert(String::from("host"), String::from("127.0.0.1:7878"));
+        if let Some(cookie) = cookie {
+            headers.insert(String::from("cookie"), String::from(cookie));
+        }
+        headers
+    }
+
+    #[test]
+    fn parse_multiple_cookies() {
+        let cookies = parse_cookies(&headers(Some("session=abc123; theme = dark")));
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies["session"], "abc123");
+        assert_eq!(cookies["theme"], "dark");
+    }
+
+    #[test]
+    fn parse_single_cookie() {
+        let cookies = parse_cookies(&headers(Some("session=abc123")));
+
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies["session"], "abc123");
+    }
+
+    #[test]
+    fn parse_without_cookie_header() {
+        assert!(parse_cookies(&headers(None)).is_empty());
+    }
+}
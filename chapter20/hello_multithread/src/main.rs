@@ -3,49 +3,86 @@ use std::fs;
 use std::io::prelude::*;
 use std::net::TcpListener;
 use std::net::TcpStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
 fn main() {
     let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
     let pool = ThreadPool::new(4);
+    // Shared by all workers: an atomic can be incremented from several threads without a Mutex
+    let connections = Arc::new(AtomicUsize::new(0));
 
     for stream in listener.incoming().take(2) {
         let stream = stream.unwrap();
+        let connections = Arc::clone(&connections);
 
-        pool.execute(|| {
-            handle_connection(stream);
+        pool.execute(move || {
+            handle_connection(stream, &connections);
         });
     }
 
     println!("Shutting down.");
 }
 
-fn handle_connection(mut stream: TcpStream) {
+fn handle_connection(mut stream: TcpStream, connections: &AtomicUsize) {
     let mut buffer = [0; 1024];
-    stream.read(&mut buffer).unwrap();
+    let read = stream.read(&mut buffer).unwrap();
+
+    let response = build_response(&buffer[..read], connections);
+
+    stream.write_all(response.as_bytes()).unwrap();
+    stream.flush().unwrap();
+}
+
+// Everything after reading the request, without the socket, so it can be called with
+// bytes held in memory. Each call counts as one handled connection, including the call
+// that serves /stats.
+fn build_response(request: &[u8], connections: &AtomicUsize) -> String {
+    let handled = connections.fetch_add(1, Ordering::SeqCst) + 1;
 
     let get = b"GET / HTTP/1.1\r\n";
     let sleep = b"GET /sleep HTTP/1.1\r\n";
+    let stats = b"GET /stats HTTP/1.1\r\n";
 
-    let (status_line, filename) = if buffer.starts_with(get) {
-        ("HTTP/1.1 200 OK", "hello.html")
-    } else if buffer.starts_with(sleep) {
+    let (status_line, contents) = if request.starts_with(get) {
+        ("HTTP/1.1 200 OK", fs::read_to_string("hello.html").unwrap())
+    } else if request.starts_with(sleep) {
         thread::sleep(Duration::from_secs(5));
-        ("HTTP/1.1 200 OK", "hello.html")
+        ("HTTP/1.1 200 OK", fs::read_to_string("hello.html").unwrap())
+    } else if request.starts_with(stats) {
+        ("HTTP/1.1 200 OK", format!("connections: {handled}"))
     } else {
-        ("HTTP/1.1 404 NOT FOUND", "404.html")
+        (
+            "HTTP/1.1 404 NOT FOUND",
+            fs::read_to_string("404.html").unwrap(),
+        )
     };
 
-    let contents = fs::read_to_string(filename).unwrap();
-
-    let response = format!(
+    format!(
         "{}\r\nContent-Length: {}\r\n\r\n{}",
         status_line,
         contents.len(),
         contents
-    );
+    )
+}
 
-    stream.write_all(response.as_bytes()).unwrap();
-    stream.flush().unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_counts_handled_connections() {
+        let connections = AtomicUsize::new(0);
+
+        build_response(b"GET / HTTP/1.1\r\n\r\n", &connections);
+        build_response(b"GET /missing HTTP/1.1\r\n\r\n", &connections);
+        let response = build_response(b"GET /stats HTTP/1.1\r\n\r\n", &connections);
+
+        assert_eq!(
+            response,
+            "HTTP/1.1 200 OK\r\nContent-Length: 14\r\n\r\nconnections: 3"
+        );
+    }
 }